@@ -0,0 +1,4 @@
+fn main() {
+    // the templates directory is embedded into the binary
+    println!("cargo:rerun-if-changed=templates");
+}
@@ -8,6 +8,8 @@ pub fn build_cli() -> Command {
         .about("A CLI UI library that is independent of package managers")
         // Disable clap's automatic help to avoid default output
         .disable_help_flag(true)
+        // We provide our own `help` subcommand below
+        .disable_help_subcommand(true)
         // Manually add a help flag to control its behavior
         .arg(
             Arg::new("help")
@@ -16,24 +18,170 @@ pub fn build_cli() -> Command {
                 .help("Print help information")
                 .action(clap::ArgAction::SetTrue), // Store true if flag is present
         )
+        .arg(
+            Arg::new("offline")
+                .long("offline")
+                .help("Only resolve remote templates from the local cache")
+                .global(true)
+                .action(clap::ArgAction::SetTrue),
+        )
         .subcommand(
             Command::new("add")
                 .about("Add a component to your Next.js project.")
                 .arg(
                     Arg::new("COMPONENT_NAME")
-                        .help("The component to add.")
+                        .help("The component, hook or block to add, a URL to a remote .tsx template, or a .vinpkg file.")
+                        .required(true)
+                        .index(1),
+                )
+                .arg(
+                    Arg::new("ROUTE")
+                        .help("Route to create a block's pages under, defaults to the block name.")
+                        .index(2),
+                )
+                .arg(
+                    Arg::new("style")
+                        .long("style")
+                        .help("Style to install components in, e.g. css-modules, overriding `style` in vin-ui.json."),
+                )
+                .arg(
+                    Arg::new("rtl")
+                        .long("rtl")
+                        .help("Rewrite physical utilities (ml-, left-, text-left, ...) to logical ones for RTL.")
+                        .action(clap::ArgAction::SetTrue),
+                )
+                .arg(
+                    Arg::new("theme")
+                        .long("theme")
+                        .help("Use a theme's colors for this component only, leaving the project's theme as it is."),
+                ),
+        )
+        .subcommand(
+            Command::new("remove")
+                .about("Remove a component and the styles it added from your project.")
+                .arg(
+                    Arg::new("COMPONENT_NAME")
+                        .help("The component to remove.")
+                        .required(true)
+                        .index(1),
+                ),
+        )
+        .subcommand(
+            Command::new("pack")
+                .about("Bundle a component and its files into a .vinpkg archive.")
+                .arg(
+                    Arg::new("COMPONENT_NAME")
+                        .help("The component to pack.")
+                        .required(true)
+                        .index(1),
+                )
+                .arg(
+                    Arg::new("output")
+                        .long("output")
+                        .short('o')
+                        .help("Where to write the package, defaults to ./<COMPONENT_NAME>.vinpkg."),
+                ),
+        )
+        .subcommand(
+            Command::new("which")
+                .about("Show where a component is resolved from, and which copies it shadows.")
+                .arg(
+                    Arg::new("COMPONENT_NAME")
+                        .help("The component to look up.")
                         .required(true)
                         .index(1),
                 ),
         )
         .subcommand(
             Command::new("list")
-                .about("List all available components."),
+                .about("List all available components, hooks and blocks.")
+                .arg(
+                    Arg::new("kind")
+                        .long("kind")
+                        .help("Only list one kind of item.")
+                        .value_parser(["component", "hook", "block"]),
+                ),
         )
         .subcommand(
             Command::new("init")
                 .about("Initialize the UI components directory."),
         )
+        .subcommand(
+            Command::new("doctor")
+                .about("Check the project is set up the way components expect."),
+        )
+        .subcommand(
+            Command::new("theme")
+                .about("Manage the color theme components are installed with.")
+                .subcommand(Command::new("list").about("List the available themes."))
+                .subcommand(
+                    Command::new("set")
+                        .about("Add a theme's colors to the global stylesheet and use it for components.")
+                        .arg(
+                            Arg::new("THEME_NAME")
+                                .help("The theme, from templates/themes or `themes` in vin-ui.json.")
+                                .required(true)
+                                .index(1),
+                        ),
+                ),
+        )
+        .subcommand(
+            Command::new("cache")
+                .about("Manage the offline cache of remote templates.")
+                .subcommand(Command::new("ls").about("List cached templates."))
+                .subcommand(Command::new("verify").about("Verify cached templates against their SHA-256."))
+                .subcommand(Command::new("clean").about("Remove all cached templates.")),
+        )
+        .subcommand(
+            Command::new("templates")
+                .about("Manage the templates copy in your config directory.")
+                .subcommand(Command::new("path").about("Show which templates directory is used and why."))
+                .subcommand(Command::new("install").about("Install templates into the config directory."))
+                .subcommand(
+                    Command::new("update")
+                        .about("Sync the config-dir templates from the bundled or development templates.")
+                        .arg(
+                            Arg::new("force")
+                                .long("force")
+                                .help("Overwrite locally modified templates too.")
+                                .action(clap::ArgAction::SetTrue),
+                        ),
+                )
+                .subcommand(Command::new("reset").about("Discard the config-dir templates and reinstall them."))
+                .subcommand(Command::new("status").about("Report modified, outdated and extra template files.")),
+        )
+        .subcommand(
+            Command::new("registry")
+                .about("Work with remote component registries.")
+                .subcommand(
+                    Command::new("mirror")
+                        .about("Snapshot a remote registry into a local templates directory.")
+                        .arg(
+                            Arg::new("URL")
+                                .help("Registry root URL or its index.json URL.")
+                                .required(true)
+                                .index(1),
+                        )
+                        .arg(
+                            Arg::new("DIR")
+                                .help("Directory to write the mirror to.")
+                                .required(true)
+                                .index(2),
+                        )
+                        .arg(
+                            Arg::new("name")
+                                .long("name")
+                                .help("Only mirror the named component (repeatable).")
+                                .action(clap::ArgAction::Append),
+                        )
+                        .arg(
+                            Arg::new("tag")
+                                .long("tag")
+                                .help("Only mirror components with this tag (repeatable).")
+                                .action(clap::ArgAction::Append),
+                        ),
+                ),
+        )
         // Explicitly add a help subcommand
         .subcommand(
             Command::new("help")
@@ -60,14 +208,39 @@ pub fn print_help() {
     println!();
     println!("{}", "SUBCOMMAND".yellow());
     println!("    add <COMPONENT_NAME>        Add a component to your project");
-    println!("    list                        List all available components");
+    println!("    add <BLOCK_NAME> [ROUTE]    Add a block, creating its pages under ROUTE");
+    println!("    remove <COMPONENT_NAME>     Remove a component from your project");
+    println!("    pack <COMPONENT_NAME>       Bundle a component into a .vinpkg file");
+    println!("    list [--kind <KIND>]        List all available components, hooks and blocks");
+    println!("    which <COMPONENT_NAME>      Show which template location a component comes from");
     println!("    init                        Initialize the components directory");
+    println!("    doctor                      Check the project setup (Tailwind, paths, ...)");
+    println!("    theme <list|set>            Manage the color theme of components");
+    println!("    cache <ls|verify|clean>     Manage the offline template cache");
+    println!("    templates <path|install|update|reset|status>");
+    println!("                                Manage the templates in your config directory");
+    println!("    registry mirror <URL> <DIR> Snapshot a remote registry locally");
     println!();
     println!("{}", "FLAGS: ".yellow());
     println!("    -h  --help                  Print this help message");
+    println!("        --offline               Only use cached remote templates");
     println!();
     println!("{}", "EXAMPLES:".yellow());
     println!("    vin-ui add Button           # Add Button component");
     println!("    vin-ui list                 # List all available components");
+    println!("    vin-ui list --kind hook     # List only the hooks");
+    println!("    vin-ui add landing-hero home");
+    println!("                                # Add a block with its page at app/home");
+    println!("    vin-ui add https://example.com/templates/button.tsx");
+    println!("                                # Add a component from a remote source");
+    println!("    vin-ui add button --style css-modules");
+    println!("                                # Add a component styled with CSS Modules");
+    println!("    vin-ui add neon-timeline --rtl");
+    println!("                                # Add a component with logical (RTL-safe) utilities");
+    println!("    vin-ui theme set ocean      # Use the ocean theme's colors");
+    println!("    vin-ui add button --theme ocean");
+    println!("                                # Add a component with a theme's colors, just this once");
+    println!("    vin-ui pack button          # Create button.vinpkg to share");
+    println!("    vin-ui add ./button.vinpkg  # Add a component from a package");
     println!();
 }
@@ -1,19 +1,81 @@
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
+use std::path::{Path, PathBuf};
 use colored::*;
 use std::process::Command;
-use anyhow::{Result, Context};
-
-use crate::utils::component::{load_component_config, add_utility};
-use crate::utils::project::{find_project_root, get_cli_root, ensure_dir, get_package_manager, get_install_command };
+use anyhow::{Result, Context, bail};
+use serde_json::{Map, Value};
+
+use crate::utils::component::{
+    load_component_config, add_utility, component_template_files, component_entry_file,
+    component_config_file, is_directory_component, list_template_files, expand_additional_file, style_files,
+};
+use crate::models::component::{FileType, TAILWIND_STYLE};
+use crate::models::config::UserConfig;
+use crate::models::project::ProjectInfo;
+use crate::utils::config::{load_project_config, load_user_config, PROJECT_CONFIG_FILE};
+use crate::utils::integrity::verify_template_files;
+use crate::utils::project::{
+    find_project_root, ensure_dir, get_package_manager, get_install_command, get_dev_flag, detect_project_info,
+    get_target_dir, split_dependency,
+};
+use crate::utils::dependencies::{check_peer, DependencySet, PeerCheck};
+use crate::utils::render::{build_template_context, install_template_file};
+use crate::utils::asset::{install_asset, public_url, rewrite_asset_references_in_file, AssetLink};
+use crate::utils::classes::{is_script_file, ClassTransforms};
+use crate::utils::block::{
+    block_file_dest, block_file_type, block_template_files, is_relative_path, load_block_config, validate_route,
+    BLOCKS_DIR,
+};
+use crate::utils::icons::IconSwap;
+use crate::utils::hook::{hook_dest_path, hook_entry_file, hook_template_files, is_hook, load_hook_config};
+use crate::utils::stylesheet::{merge_component_css, resolve_global_css};
+use crate::utils::tailwind::{check_tailwind, offer_content_glob, TailwindCheck};
+use crate::utils::tailwind_config::extend_theme;
+use crate::utils::theme::{load_theme, merge_theme_css, theme_substitutions};
+use crate::utils::template_finder::{find_block_dir, find_component_dir, find_hook_dir, get_templates_dir};
+use crate::utils::overlay::{apply_overlays, overlay_files_for};
+use crate::utils::package::{is_package, stage_package};
+use crate::utils::remote::{is_remote, stage_remote_component};
 use crate::utils::ui::{print_success, print_error, print_warning, print_info, confirm};
 
+// Components take precedence over hooks and blocks with the same name
+pub fn add_item(name: &str, route: Option<&str>, style: Option<&str>, theme: Option<&str>, rtl: bool, offline: bool) -> Result<()> {
+    if !is_remote(name) && !is_package(name) && find_component_dir(name).is_none() {
+        if let Some(template_dir) = find_hook_dir(name) {
+            return add_hook(&template_dir, name);
+        }
+        if let Some(template_dir) = find_block_dir(name) {
+            return add_block(&template_dir, name, route.unwrap_or(name), style, theme, rtl, offline);
+        }
+    }
+
+    if route.is_some() {
+        print_warning("A route only applies to blocks and was ignored");
+    }
+    add_component(name, style, theme, rtl, offline)
+}
+
+
 // Utils compoenets
-pub fn add_component(component_name: &str) -> Result<()> {
-    let cli_root = get_cli_root();
-    let template_dir = cli_root.join("templates");
+fn add_component(component_name: &str, style: Option<&str>, theme: Option<&str>, rtl: bool, offline: bool) -> Result<()> {
+    // remote components and packages are staged into a local dir with the templates layout,
+    // removed when the add is done
+    let staged = if is_remote(component_name) {
+        Some(stage_remote_component(component_name, offline)?)
+    } else if is_package(component_name) {
+        Some(stage_package(Path::new(component_name))?)
+    } else {
+        None
+    };
+    let (template_dir, component_name) = match &staged {
+        Some((staging_dir, name)) => (staging_dir.path().to_path_buf(), name.clone()),
+        None => (find_component_dir(component_name).unwrap_or_else(get_templates_dir), component_name.to_string()),
+    };
+    let component_name = component_name.as_str();
 
-    let component_path = template_dir.join(format!("{}.tsx", component_name));
-    let config_path = template_dir.join(format!("{}.json", component_name));
+    let component_path = template_dir.join(component_entry_file(&template_dir, component_name));
+    let config_path = template_dir.join(component_config_file(&template_dir, component_name));
 
     if !component_path.exists() && !config_path.exists() {
         print_error(&format!("Neither component '{}' nor its config found in templates!", component_name));
@@ -28,6 +90,47 @@ pub fn add_component(component_name: &str) -> Result<()> {
     }
 
 
+    // verify everything we're about to copy before touching the project
+    let config = load_component_config(&config_path).ok().flatten();
+    let user_config = load_user_config()?;
+    let template_files = component_template_files(&template_dir, component_name, config.as_ref());
+
+    verify_files(&template_dir, component_name, &template_files, &user_config)?;
+
+
+    // organization overlays are applied on top of the verified upstream template
+    let overlaid = apply_overlays(&template_dir, component_name, &user_config.overlays)?;
+    let template_dir = overlaid.as_ref().map(|staging_dir| staging_dir.path().to_path_buf()).unwrap_or(template_dir);
+    let component_path = template_dir.join(component_entry_file(&template_dir, component_name));
+    let config = load_component_config(&template_dir.join(component_config_file(&template_dir, component_name)))
+        .ok()
+        .flatten();
+
+    for overlay_file in overlay_files_for(component_name, &user_config.overlays) {
+        print_info(&format!("Applying overlay {}", overlay_file.display()));
+    }
+
+
+    // expand glob sources up front, so a pattern matching nothing fails before anything is written
+    let mut additional_files = Vec::new();
+    for file_info in config.as_ref().and_then(|config| config.additional_files.as_ref()).into_iter().flatten() {
+        additional_files.extend(expand_additional_file(&template_dir.join("utils"), file_info)?);
+    }
+
+
+    // hooks the component uses come from the same templates dir
+    let hooks = config.as_ref().and_then(|config| config.hooks.clone()).unwrap_or_default();
+    let mut dependencies = config.as_ref().map(DependencySet::of).unwrap_or_default();
+    for hook in &hooks {
+        if !is_hook(&template_dir, hook) {
+            bail!("Component '{}' uses hook '{}', which is not in {}", component_name, hook, template_dir.display());
+        }
+        if let Some(hook_config) = load_hook_config(&template_dir, hook)? {
+            dependencies.merge(DependencySet::of(&hook_config));
+        }
+    }
+
+
     //find Next.js project root
     let project_root = match find_project_root() {
         Some(root) => root,
@@ -37,30 +140,85 @@ pub fn add_component(component_name: &str) -> Result<()> {
         }
     };
 
-    //Check for comonents/ui dir
-    let components_dir = project_root.join("components");
-    let ui_dir = components_dir.join("ui");
-    let lib_dir = project_root.join("lib");
-
-    //create if not exists
-    if !components_dir.exists() {
-        print_warning("components directory not found");
-        let create = confirm("Would  you like to create components directory?", true);
-
-        if create {
-            ensure_dir(&components_dir)?;
-            print_success(&format!("created a components directory at {}", components_dir.display()));
-        } else {
-            print_info("Operation Cancelled.");
-            return Ok(());
+    // values for template variables and conditional sections
+    let project_info = detect_project_info(&project_root);
+    let variables = config.as_ref().and_then(|config| config.variables.as_deref()).unwrap_or_default();
+    let context = build_template_context(component_name, &project_info, variables);
+    let project_config = load_project_config(&project_root)?;
+
+    // components can come in other styles than Tailwind, like CSS Modules or plain CSS
+    let style_name = style.or(project_config.style.as_deref()).filter(|name| *name != TAILWIND_STYLE);
+    let style_variant = match style_name {
+        Some(name) => match config.as_ref().and_then(|config| config.styles.as_ref()).and_then(|styles| styles.get(name)) {
+            Some(style_variant) => {
+                print_info(&format!("Using the '{}' style of '{}'", name, component_name));
+                Some(style_variant)
+            }
+            None => {
+                print_warning(&format!("'{}' has no '{}' style, installing its Tailwind version", component_name, name));
+                None
+            }
+        },
+        None => None,
+    };
+    let is_tailwind = style_variant.is_none();
+
+    // the files of the styles not picked are left out
+    let style_files_used = style_variant.and_then(|style| style.files.clone()).unwrap_or_default();
+    let unused_style_files: Vec<String> = config.as_ref()
+        .map(style_files)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|file| !style_files_used.contains(file))
+        .collect();
+
+    // templates written for another Tailwind version can ship a variant for this one
+    let mut variant_file = None;
+    let mut variant_files = Vec::new();
+    if let Some(requirement) = config.as_ref().and_then(|config| config.tailwind.as_ref()) {
+        variant_files.extend(requirement.variants.iter().flat_map(|variants| variants.values().cloned()));
+
+        match check_tailwind(requirement, &project_info) {
+            // other styles don't depend on Tailwind
+            _ if !is_tailwind => {}
+            TailwindCheck::Compatible => {}
+            TailwindCheck::Variant(version, file) => {
+                print_info(&format!("Using the Tailwind v{} variant of '{}'", version, component_name));
+                variant_file = Some(file);
+            }
+            TailwindCheck::NotDetected => print_warning(&format!(
+                "Tailwind CSS was not detected in this project, '{}' is styled for Tailwind v{}",
+                component_name, requirement.version
+            )),
+            TailwindCheck::UnknownVersion => print_warning(&format!(
+                "Couldn't tell which Tailwind version this project uses, '{}' is styled for Tailwind v{}",
+                component_name, requirement.version
+            )),
+            TailwindCheck::Mismatch(version) => print_warning(&format!(
+                "'{}' is styled for Tailwind v{} but this project uses v{}, some classes may not work",
+                component_name, requirement.version, version
+            )),
         }
     }
 
+    //target dirs come from vin-ui.json paths, or the defaults (components/ui, lib, ...)
+    let target_dir = |file_type: FileType| get_target_dir(&project_root, &project_info, &project_config, file_type);
+    // a theme given to add only applies to this component, its colors go straight into
+    // the classes and vin-ui.json and the stylesheet are left alone
+    let theme_name = theme.or(project_config.theme.as_deref());
+    let inline_theme = theme.is_some_and(|name| project_config.theme.as_deref() != Some(name));
+    let theme = match theme_name {
+        Some(theme_name) => Some(load_theme(&project_config, theme_name)?),
+        None => None,
+    };
+    let icons = IconSwap::new(&project_config)?;
+    let ui_dir = target_dir(FileType::Component);
+    let lib_dir = target_dir(FileType::Lib);
 
-    //for ui directory
+    //create if not exists
     if !ui_dir.exists() {
-        print_warning("componens/ui directory not found");
-        let create = confirm("Would  you like to create ui directory?", true);
+        print_warning(&format!("{} directory not found", ui_dir.display()));
+        let create = confirm("Would  you like to create it?", true);
 
         if create {
             ensure_dir(&ui_dir)?;
@@ -72,9 +230,96 @@ pub fn add_component(component_name: &str) -> Result<()> {
     }
 
 
-    let dest_file = ui_dir.join(format!("{}.tsx", component_name));
+    // classes in the component are purged unless Tailwind scans everywhere its scripts go:
+    // the ui dir, and the dirs its hooks, utilities and additional files end up in
+    if is_tailwind {
+        let mut script_dirs = BTreeSet::from([ui_dir.clone()]);
+        if !hooks.is_empty() {
+            script_dirs.insert(target_dir(FileType::Hook));
+        }
+        if config.as_ref().and_then(|config| config.utils.as_ref()).is_some_and(|utils| !utils.is_empty()) {
+            script_dirs.insert(lib_dir.clone());
+        }
+        for file in additional_files.iter().filter(|file| file.file_type != FileType::Asset && is_script_file(Path::new(&file.dest))) {
+            script_dirs.insert(target_dir(file.file_type));
+        }
+        for dir in &script_dirs {
+            offer_content_glob(&project_root, &project_info, dir)?;
+        }
+    }
+
+
+    // directory components are installed as a unit into components/ui/<name>/
+    let is_directory = is_directory_component(&template_dir, component_name);
+    let component_files = if is_directory {
+        list_template_files(&template_dir.join(component_name))?
+    } else {
+        BTreeMap::new()
+    };
 
-    if dest_file.exists() {
+    // assets go to public/<name>/, and the files using them are pointed at their URLs
+    let assets_dir = target_dir(FileType::Asset).join(component_name);
+    let public_dir = project_root.join("public");
+    let mut assets = Vec::new();
+    for file in component_files.keys().filter(|file| FileType::from_extension(file) == FileType::Asset) {
+        assets.push(AssetLink {
+            source: format!("{}/{}", component_name, file),
+            path: assets_dir.join(file),
+            url: public_url(&public_dir, &assets_dir, file),
+        });
+    }
+    for file in additional_files.iter().filter(|file| file.file_type == FileType::Asset) {
+        assets.push(AssetLink {
+            source: format!("utils/{}", file.source),
+            path: assets_dir.join(&file.dest),
+            url: public_url(&public_dir, &assets_dir, &file.dest),
+        });
+    }
+    warn_unserved_assets(&assets, &assets_dir);
+
+    // the project's Tailwind prefix goes on the component's classes, but not on
+    // the ones its own stylesheets define
+    let mut class_transforms = ClassTransforms::new(&project_info);
+    class_transforms.rtl = is_tailwind && (rtl || project_config.rtl);
+    if !is_tailwind {
+        class_transforms.prefix = None;
+    }
+    for (_, source_path) in component_files.iter().filter(|(file, _)| file.ends_with(".css")) {
+        let css = fs::read_to_string(source_path)
+            .context(format!("Failed to read stylesheet: {}", source_path.display()))?;
+        class_transforms.keep_classes_of(&css);
+    }
+    for file in additional_files.iter().filter(|file| file.source.ends_with(".css")) {
+        let source_path = template_dir.join("utils").join(&file.source);
+        let css = fs::read_to_string(&source_path)
+            .context(format!("Failed to read stylesheet: {}", source_path.display()))?;
+        class_transforms.keep_classes_of(&css);
+    }
+    for rules in config.as_ref().and_then(|config| config.css.as_ref()).and_then(|css| css.layers.as_ref()).into_iter().flat_map(|layers| layers.values()) {
+        class_transforms.keep_classes_of(rules);
+    }
+    // with a theme set, the colors the component declares point at the theme's variables
+    let colors = config.as_ref().and_then(|config| config.colors.as_ref()).filter(|_| is_tailwind);
+    if let (Some(theme), Some(colors)) = (&theme, colors) {
+        class_transforms.colors = theme_substitutions(colors, theme, inline_theme);
+    }
+    if !class_transforms.colors.is_empty() {
+        print_info(&format!(
+            "Using the colors of theme '{}' for '{}'",
+            theme_name.unwrap_or_default(), component_name
+        ));
+    }
+    if let Some(prefix) = &class_transforms.prefix {
+        print_info(&format!("Applying the Tailwind prefix '{}' to the classes of '{}'", prefix, component_name));
+    }
+
+    let dest_path = if is_directory {
+        ui_dir.join(component_name)
+    } else {
+        ui_dir.join(format!("{}.tsx", component_name))
+    };
+
+    if dest_path.exists() {
         let overwrite = confirm(
             &format!("component {} already exists, Overwrite?", component_name),
             false
@@ -86,138 +331,558 @@ pub fn add_component(component_name: &str) -> Result<()> {
             }
     }
 
-    //copy the component file
-    fs::copy(&component_path, &dest_file)
-        .context(format!("Failed to copy component file to {}", dest_file.display()))?;
+    if is_directory {
+        //copy every file of the component, keeping its relative layout
+        let config_file = format!("{}.json", component_name);
+        let entry_file = format!("{}.tsx", component_name);
+        for (file, source_path) in &component_files {
+            if *file == config_file || variant_files.contains(file) || unused_style_files.contains(file) {
+                continue;
+            }
+
+            let source_path = match (style_variant, &variant_file) {
+                (Some(style), _) if *file == entry_file => template_dir.join(component_name).join(&style.entry),
+                (None, Some(variant)) if *file == entry_file => template_dir.join(component_name).join(variant),
+                _ => source_path.clone(),
+            };
+
+            let is_asset = FileType::from_extension(file) == FileType::Asset;
+            let dest_file = if is_asset { assets_dir.join(file) } else { dest_path.join(file) };
+            if let Some(parent) = dest_file.parent() {
+                ensure_dir(parent)?;
+            }
+
+            if is_asset {
+                install_asset(&source_path, &dest_file)?;
+            } else {
+                install_template_file(&source_path, &dest_file, &context)?;
+                rewrite_asset_references_in_file(&dest_file, &format!("{}/{}", component_name, file), &assets)?;
+                class_transforms.apply_to_file(&dest_file)?;
+                if let Some(icons) = &icons {
+                    icons.apply_to_file(&dest_file)?;
+                }
+            }
+        }
+    } else {
+        //copy the component file
+        let source_path = match (style_variant, &variant_file) {
+            (Some(style), _) => template_dir.join(&style.entry),
+            (None, Some(variant)) => template_dir.join(variant),
+            (None, None) => component_path.clone(),
+        };
+        install_template_file(&source_path, &dest_path, &context)?;
+        rewrite_asset_references_in_file(&dest_path, &format!("{}.tsx", component_name), &assets)?;
+        class_transforms.apply_to_file(&dest_path)?;
+        if let Some(icons) = &icons {
+            icons.apply_to_file(&dest_path)?;
+        }
+
+        // the style's stylesheets go next to the component, e.g. button.module.css
+        for file in &style_files_used {
+            install_template_file(&template_dir.join(file), &ui_dir.join(file), &context)?;
+        }
+    }
 
     print_success(&format!(
             "Component '{}' successfully installed to {}",
             component_name,
-            dest_file.display()
+            dest_path.display()
     ));
+    report_unconverted(&class_transforms, &project_root);
 
+    // icons were swapped to the project's library, and so is the package installed for them
+    if let Some(icons) = &icons {
+        dependencies.dependencies = icons.dependencies(&dependencies.dependencies);
+        report_unmapped_icons(icons, &project_root);
+    }
 
 
 
+    // variables, keyframes and layers the component needs go into the global stylesheet
+    if let Some(css) = config.as_ref().and_then(|config| config.css.as_ref()) {
+        match resolve_global_css(&project_root, &project_config) {
+            Some(stylesheet) if stylesheet.is_file() => {
+                if merge_component_css(&stylesheet, component_name, css)? {
+                    print_success(&format!("Added the styles '{}' needs to {}", component_name, stylesheet.display()));
+                } else {
+                    print_info(&format!("{} already has the styles '{}' needs", stylesheet.display(), component_name));
+                }
+            }
+            Some(stylesheet) => print_warning(&format!(
+                "Global stylesheet {} not found, skipped the styles '{}' needs",
+                stylesheet.display(), component_name
+            )),
+            None => print_warning(&format!(
+                "No global stylesheet found, set \"css\" in {} to add the styles '{}' needs",
+                PROJECT_CONFIG_FILE, component_name
+            )),
+        }
+    }
 
+    // the theme's variables, for when vin-ui.json was edited rather than using `theme set`
+    if let Some(theme) = theme.as_ref().filter(|_| !class_transforms.colors.is_empty() && !inline_theme) {
+        if let Some(stylesheet) = resolve_global_css(&project_root, &project_config).filter(|path| path.is_file()) {
+            if merge_theme_css(&stylesheet, theme)? {
+                print_success(&format!("Added the theme's colors to {}", stylesheet.display()));
+            }
+        }
+    }
 
-    //Check for component config file that specifies dependencies
-    if let Ok(Some(config)) = load_component_config(&config_path) {
-        // check if d comp. has dependencies
-        if let Some(dependencies) = config.dependencies {
-            if !dependencies.is_empty() {
-                print_info(&format!(
-                        "Component '{}' requires the following dependencies:",
-                        component_name
-                ));
+    // keyframes, animations, colors, ... for the theme in tailwind.config
+    if let Some(extend) = config.as_ref().and_then(|config| config.tailwind_extend.as_ref()).filter(|_| is_tailwind) {
+        extend_tailwind_theme(&project_info, component_name, extend);
+    }
 
-                for dep in &dependencies {
-                    println!("  - {}", dep);
-                }
+    install_dependencies(&project_root, component_name, &dependencies)?;
 
-                let insall_deps = confirm("Would ya like to install these deps..?", true);
+    // Check if the component requires utility functions
+    if let Some(utils) = config.and_then(|config| config.utils) {
+        if !utils.is_empty() {
+            print_info(&format!(
+                    "Component {} requires the following utilities.",
+                    component_name
+            ));
 
-                if insall_deps {
-                    let package_manager = get_package_manager(&project_root);
-                    let install_arg = get_install_command(package_manager);
+            for util in &utils {
+                println!("-  {}", util);
+            }
 
-                    print_info("Installing deps..");
+            let install_utils = confirm("Would ya like to install these following utilities?", true);
 
-                    let status = Command::new(package_manager)
-                        .current_dir(&project_root)
-                        .arg(install_arg)
-                        .args(&dependencies)
-                        .status()
-                        .context("Failed to run install comands")?;
+            if install_utils {
+                // Create lib/utils.ts if it doesn't exist
+                ensure_dir(&lib_dir)?;
 
-                    if status.success() {
-                        print_success("Yoo hoo, Deps Installed!");
-                    } else {
-                        print_error("Sorry :( Failed to install Deps..");
-                    }
+                // Add each utility functions
+                for util in &utils {
+                    add_utility(&lib_dir, util, &template_dir)?;
                 }
+
+                print_success(&format!(
+                        "utilities functions added to {}",
+                        lib_dir.join("utils.ts").display()
+                ));
             }
         }
+    }
 
-        // Check if the component requires utility functions
-        if let Some(utils) = config.utils {
-            if !utils.is_empty() {
-                print_info(&format!(
-                        "Component {} requires the following utilities.",
-                        component_name
-                ));
 
-                for util in &utils {
-                    println!("-  {}", util);
-                }
+    // hooks are shared between components, so ones already in the project are kept
+    if !hooks.is_empty() {
+        let hooks_dir = target_dir(FileType::Hook);
+        ensure_dir(&hooks_dir)?;
+
+        for hook in &hooks {
+            let dest_path = hook_dest_path(&template_dir, &hooks_dir, hook);
+            if dest_path.exists() {
+                print_info(&format!("Hook '{}' already exists at {}, keeping it", hook, dest_path.display()));
+                continue;
+            }
+
+            install_template_file(&template_dir.join(hook_entry_file(&template_dir, hook)), &dest_path, &context)?;
+            print_success(&format!("Hook '{}' installed to {}", hook, dest_path.display()));
+        }
+    }
 
-                let install_utils = confirm("Would ya like to install these following utilities?", true);
 
-                if install_utils {
-                    // Create lib/utils.ts if it doesn't exist
-                    let lib_dir = project_root.join("lib");
-                    ensure_dir(&lib_dir)?;
+    if !additional_files.is_empty() {
+        print_info(&format!(
+            "Component '{}' requires the following additional files:",
+            component_name
+        ));
 
-                    // Add each utility functions
-                    for util in &utils {
-                        add_utility(&lib_dir, &util, &template_dir)?;
-                    }
+        for file in &additional_files {
+            println!("  - {}", file.source);
 
-                    print_success(&format!(
-                            "utilities functions added to {}",
-                            lib_dir.join("utils.ts").display()
-                    ));
+            // Determine destination based on the declared file type
+            let source_path = template_dir.join("utils").join(&file.source);
+            let is_asset = file.file_type == FileType::Asset;
+            let dest_path = if is_asset { assets_dir.join(&file.dest) } else { target_dir(file.file_type).join(&file.dest) };
+
+            // Create parent directories if needed
+            if let Some(parent) = dest_path.parent() {
+                ensure_dir(parent)?;
+            }
+
+            // Copy the file
+            if source_path.exists() && is_asset {
+                install_asset(&source_path, &dest_path)?;
+                print_success(&format!(
+                    "Asset '{}' installed to {}",
+                    file.source,
+                    dest_path.display()
+                ));
+            } else if source_path.exists() {
+                install_template_file(&source_path, &dest_path, &context)?;
+                rewrite_asset_references_in_file(&dest_path, &format!("utils/{}", file.source), &assets)?;
+                class_transforms.apply_to_file(&dest_path)?;
+                if let Some(icons) = &icons {
+                    icons.apply_to_file(&dest_path)?;
                 }
+                print_success(&format!(
+                    "Additional file '{}' installed to {}",
+                    file.source,
+                    dest_path.display()
+                ));
+            } else {
+                print_warning(&format!("Additional file '{}' not found in templates/utils", file.source));
             }
-        } 
+        }
+    }
 
+    println!("\n{} Component installation complete! 🚀", "SUCCESS:".green().bold());
+    Ok(())
+}
 
-        if let Some(additional_files) = config.additional_files {
-            print_info(&format!(
-                "Component '{}' requires the following additional files:",
-                component_name
-            ));
-            
-            for file_info in &additional_files {
-                println!("  - {}", file_info.source);
-                
-                // Determine destination based on file extension
-                let source_path = template_dir.join("utils").join(&file_info.source);
-                let dest_path = if file_info.source.ends_with(".tsx") || 
-                                  file_info.source.ends_with(".css") ||
-                                  file_info.source.ends_with(".scss") {
-                    // Component files go to components/ui
-                    ui_dir.join(&file_info.dest.clone().unwrap_or(file_info.source.clone()))
-                } else if file_info.source.ends_with(".ts") {
-                    // Utility files go to lib
-                    ensure_dir(&lib_dir)?;
-                    lib_dir.join(&file_info.dest.clone().unwrap_or(file_info.source.clone()))
+
+// Verify template files against the templates dir's index, if it has one
+fn verify_files(template_dir: &Path, item_name: &str, files: &[String], user_config: &UserConfig) -> Result<()> {
+    let verification = verify_template_files(template_dir, files, user_config)
+        .context(format!("Refusing to install '{}': template verification failed", item_name))?;
+
+    if let Some(verification) = verification {
+        match verification.signed_by {
+            Some(key) => print_info(&format!(
+                "Verified {} template files against an index signed by '{}'",
+                verification.verified, key
+            )),
+            None => print_info(&format!(
+                "Verified {} template files against the template index",
+                verification.verified
+            )),
+        }
+    }
+
+    Ok(())
+}
+
+
+fn install_dependencies(project_root: &Path, item_name: &str, dependencies: &DependencySet) -> Result<()> {
+    // peers are left to the project, only say when they don't match
+    for spec in &dependencies.peer_dependencies {
+        let wanted = match split_dependency(spec) {
+            (package, Some(range)) => format!("{} {}", package, range),
+            (package, None) => package.to_string(),
+        };
+        match check_peer(project_root, spec) {
+            PeerCheck::Satisfied => {}
+            PeerCheck::Missing => print_warning(&format!(
+                "'{}' needs {} as a peer dependency, which package.json doesn't list",
+                item_name, wanted
+            )),
+            PeerCheck::Mismatch(version) => print_warning(&format!(
+                "'{}' needs {}, the project has {}",
+                item_name, wanted, version
+            )),
+            PeerCheck::Unknown(version) => print_warning(&format!(
+                "Couldn't check {} against the project's {} for '{}'",
+                wanted, version, item_name
+            )),
+        }
+    }
+
+    if dependencies.dependencies.is_empty() && dependencies.dev_dependencies.is_empty() {
+        return Ok(());
+    }
+
+    print_info(&format!(
+            "Component '{}' requires the following dependencies:",
+            item_name
+    ));
+
+    for dep in &dependencies.dependencies {
+        println!("  - {}", dep);
+    }
+    for dep in &dependencies.dev_dependencies {
+        println!("  - {} (dev)", dep);
+    }
+
+    let insall_deps = confirm("Would ya like to install these deps..?", true);
+
+    if insall_deps {
+        let package_manager = get_package_manager(project_root);
+
+        print_info("Installing deps..");
+
+        let mut installed = true;
+        if !dependencies.dependencies.is_empty() {
+            installed &= run_install(project_root, package_manager, &[], &dependencies.dependencies)?;
+        }
+        if installed && !dependencies.dev_dependencies.is_empty() {
+            installed &= run_install(project_root, package_manager, &[get_dev_flag(package_manager)], &dependencies.dev_dependencies)?;
+        }
+
+        if installed {
+            print_success("Yoo hoo, Deps Installed!");
+        } else {
+            print_error("Sorry :( Failed to install Deps..");
+        }
+    }
+
+    Ok(())
+}
+
+// Run the package manager's install command, returns whether it succeeded
+fn run_install(project_root: &Path, package_manager: &str, flags: &[&str], dependencies: &[String]) -> Result<bool> {
+    let status = Command::new(package_manager)
+        .current_dir(project_root)
+        .arg(get_install_command(package_manager))
+        .args(flags)
+        .args(dependencies)
+        .status()
+        .context("Failed to run install comands")?;
+    Ok(status.success())
+}
+
+
+// Add a hook from templates/hooks on its own
+fn add_hook(template_dir: &Path, hook_name: &str) -> Result<()> {
+    let user_config = load_user_config()?;
+    verify_files(template_dir, hook_name, &hook_template_files(template_dir, hook_name), &user_config)?;
+    let config = load_hook_config(template_dir, hook_name)?;
+
+    let project_root = match find_project_root() {
+        Some(root) => root,
+        None => {
+            print_error("No Next.js project found in the current directory or its parent.");
+            return Ok(());
+        }
+    };
+
+    let project_info = detect_project_info(&project_root);
+    let variables = config.as_ref().and_then(|config| config.variables.as_deref()).unwrap_or_default();
+    let context = build_template_context(hook_name, &project_info, variables);
+    let project_config = load_project_config(&project_root)?;
+    let hooks_dir = get_target_dir(&project_root, &project_info, &project_config, FileType::Hook);
+
+    let dest_path = hook_dest_path(template_dir, &hooks_dir, hook_name);
+    if dest_path.exists() && !confirm(&format!("hook {} already exists, Overwrite?", hook_name), false) {
+        print_info("Operation Cancelled.");
+        return Ok(());
+    }
+
+    offer_content_glob(&project_root, &project_info, &hooks_dir)?;
+
+    ensure_dir(&hooks_dir)?;
+    install_template_file(&template_dir.join(hook_entry_file(template_dir, hook_name)), &dest_path, &context)?;
+    print_success(&format!("Hook '{}' successfully installed to {}", hook_name, dest_path.display()));
+
+    install_dependencies(&project_root, hook_name, &config.as_ref().map(DependencySet::of).unwrap_or_default())?;
+
+    println!("\n{} Hook installation complete! 🚀", "SUCCESS:".green().bold());
+    Ok(())
+}
+
+
+// Add a block: its components, then its page and layout files under the route
+fn add_block(template_dir: &Path, block_name: &str, route: &str, style: Option<&str>, theme: Option<&str>, rtl: bool, offline: bool) -> Result<()> {
+    validate_route(route)?;
+    let config = load_block_config(template_dir, block_name)?;
+    let user_config = load_user_config()?;
+    verify_files(template_dir, block_name, &block_template_files(block_name, &config), &user_config)?;
+
+    let components = config.components.clone().unwrap_or_default();
+    for component in &components {
+        if find_component_dir(component).is_none() {
+            bail!("Block '{}' uses component '{}', which is not in any templates directory", block_name, component);
+        }
+    }
+
+    let block_dir = template_dir.join(BLOCKS_DIR).join(block_name);
+    for file in &config.files {
+        if !block_dir.join(&file.source).is_file() {
+            bail!("Block '{}' is missing its file '{}'", block_name, file.source);
+        }
+    }
+
+    let project_root = match find_project_root() {
+        Some(root) => root,
+        None => {
+            print_error("No Next.js project found in the current directory or its parent.");
+            return Ok(());
+        }
+    };
+
+    let project_info = detect_project_info(&project_root);
+    let mut context = build_template_context(block_name, &project_info, config.variables.as_deref().unwrap_or_default());
+    context.insert("route".to_string(), route.to_string());
+    let project_config = load_project_config(&project_root)?;
+
+    // work out every destination first, so existing routes are caught before anything is installed
+    // assets go to public/<block>/ like a component's
+    let target_dir = |file_type: FileType| get_target_dir(&project_root, &project_info, &project_config, file_type);
+    let assets_dir = target_dir(FileType::Asset).join(block_name);
+    let mut files = Vec::new();
+    let mut assets = Vec::new();
+    for file in &config.files {
+        match block_file_dest(file, route, &project_info) {
+            Some(dest) if is_relative_path(&dest) => {
+                let file_type = block_file_type(file);
+                let dest_path = if file_type == FileType::Asset {
+                    assets.push(AssetLink {
+                        source: format!("{}/{}/{}", BLOCKS_DIR, block_name, file.source),
+                        path: assets_dir.join(&dest),
+                        url: public_url(&project_root.join("public"), &assets_dir, &dest),
+                    });
+                    assets_dir.join(dest)
                 } else {
-                    // Other files use specified destination or default to components/ui
-                    ui_dir.join(&file_info.dest.clone().unwrap_or(file_info.source.clone()))
+                    target_dir(file_type).join(dest)
                 };
-                
-                // Create parent directories if needed
-                if let Some(parent) = dest_path.parent() {
-                    ensure_dir(parent)?;
-                }
-                
-                // Copy the file
-                if source_path.exists() {
-                    fs::copy(&source_path, &dest_path)
-                        .context(format!("Failed to copy {} to {}", source_path.display(), dest_path.display()))?;
-                    print_success(&format!(
-                        "Additional file '{}' installed to {}",
-                        file_info.source,
-                        dest_path.display()
-                    ));
-                } else {
-                    print_warning(&format!("Additional file '{}' not found in templates/utils", file_info.source));
-                }
+                files.push((file, dest_path));
             }
+            Some(dest) => bail!("Block file '{}' has an invalid destination '{}'", file.source, dest),
+            None => print_warning(&format!("Skipping '{}', the Pages router has no layouts", file.source)),
         }
     }
 
-    println!("\n{} Component installation complete! 🚀", "SUCCESS:".green().bold());
+    warn_unserved_assets(&assets, &assets_dir);
+
+    let existing: Vec<&PathBuf> = files.iter().map(|(_, dest)| dest).filter(|dest| dest.exists()).collect();
+    if !existing.is_empty() {
+        print_warning("These files already exist:");
+        for dest in &existing {
+            println!("  - {}", dest.display());
+        }
+
+        if !confirm("Overwrite them?", false) {
+            print_info("Operation Cancelled.");
+            return Ok(());
+        }
+    }
+
+    // block files are written with Tailwind, there's nothing to rewrite without it
+    let mut class_transforms = ClassTransforms::new(&project_info);
+    class_transforms.rtl = project_info.tailwind.is_some() && (rtl || project_config.rtl);
+    let icons = IconSwap::new(&project_config)?;
+    for (file, _) in files.iter().filter(|(file, _)| file.source.ends_with(".css")) {
+        let source_path = block_dir.join(&file.source);
+        let css = fs::read_to_string(&source_path)
+            .context(format!("Failed to read stylesheet: {}", source_path.display()))?;
+        class_transforms.keep_classes_of(&css);
+    }
+
+    // the page and layout classes are purged unless Tailwind scans the dirs they go to
+    let script_dirs: BTreeSet<PathBuf> = files.iter()
+        .map(|(file, dest)| (block_file_type(file), dest))
+        .filter(|(file_type, dest)| *file_type != FileType::Asset && is_script_file(dest))
+        .map(|(file_type, _)| target_dir(file_type))
+        .collect();
+    for dir in &script_dirs {
+        offer_content_glob(&project_root, &project_info, dir)?;
+    }
+
+    for component in &components {
+        print_info(&format!("Adding component '{}' for block '{}'", component, block_name));
+        add_component(component, style, theme, rtl, offline)?;
+    }
+
+    for (file, dest_path) in &files {
+        if let Some(parent) = dest_path.parent() {
+            ensure_dir(parent)?;
+        }
+
+        let source_path = block_dir.join(&file.source);
+        if block_file_type(file) == FileType::Asset {
+            install_asset(&source_path, dest_path)?;
+        } else {
+            install_template_file(&source_path, dest_path, &context)?;
+            let from_file = format!("{}/{}/{}", BLOCKS_DIR, block_name, file.source);
+            rewrite_asset_references_in_file(dest_path, &from_file, &assets)?;
+            class_transforms.apply_to_file(dest_path)?;
+            if let Some(icons) = &icons {
+                icons.apply_to_file(dest_path)?;
+            }
+        }
+        print_success(&format!("Created {}", dest_path.display()));
+    }
+
+    report_unconverted(&class_transforms, &project_root);
+
+    let mut dependencies = DependencySet::new(
+        config.dependencies.as_ref(), config.dev_dependencies.as_ref(), config.peer_dependencies.as_ref()
+    );
+    if let Some(icons) = &icons {
+        dependencies.dependencies = icons.dependencies(&dependencies.dependencies);
+        report_unmapped_icons(icons, &project_root);
+    }
+    install_dependencies(&project_root, block_name, &dependencies)?;
+
+    println!("\n{} Block '{}' installed at route '{}'! 🚀", "SUCCESS:".green().bold(), block_name, route);
     Ok(())
 }
+
+
+// Assets outside public/ have no URL, only imports of them can be pointed at the installed files
+fn warn_unserved_assets(assets: &[AssetLink], assets_dir: &Path) {
+    if assets.iter().any(|asset| asset.url.is_none()) {
+        print_warning(&format!(
+            "{} is not in public/, so its assets aren't served at a URL. Imports of them are updated, other references are left as they are",
+            assets_dir.display()
+        ));
+    }
+}
+
+
+// Classes the RTL conversion had to leave physical, like `space-x-4` or `bg-gradient-to-r`
+fn report_unconverted(class_transforms: &ClassTransforms, project_root: &Path) {
+    let unconverted = class_transforms.unconverted();
+    if unconverted.is_empty() {
+        return;
+    }
+
+    print_warning("These classes have no logical equivalent and were left as they are, check them in RTL:");
+    print_files(unconverted, project_root);
+}
+
+// Icons the project's library has no counterpart for, which stay imported from their own
+fn report_unmapped_icons(icons: &IconSwap, project_root: &Path) {
+    let unmapped = icons.unmapped();
+    if unmapped.is_empty() {
+        return;
+    }
+
+    print_warning("These icons have no counterpart in the project's icon library and still use their own:");
+    print_files(unmapped, project_root);
+}
+
+fn print_files(items: BTreeMap<PathBuf, BTreeSet<String>>, project_root: &Path) {
+    for (file, items) in items {
+        let file = file.strip_prefix(project_root).unwrap_or(&file);
+        println!("  {}: {}", file.display(), items.into_iter().collect::<Vec<_>>().join(", "));
+    }
+}
+
+
+// Edit tailwind.config, or say what to add by hand when it can't be done safely
+fn extend_tailwind_theme(project_info: &ProjectInfo, component_name: &str, extend: &Map<String, Value>) {
+    let config_file = project_info.tailwind.as_ref().and_then(|tailwind| tailwind.config_file.as_ref());
+    let manual = serde_json::to_string_pretty(extend).unwrap_or_default();
+
+    let Some(config_file) = config_file else {
+        if project_info.tailwind_version() == Some(4) {
+            print_warning(&format!(
+                "'{}' extends the Tailwind theme, add this to an @theme block in your stylesheet:\n{}",
+                component_name, manual
+            ));
+        } else {
+            print_warning(&format!(
+                "No tailwind.config found, '{}' needs these theme.extend entries:\n{}",
+                component_name, manual
+            ));
+        }
+        return;
+    };
+
+    match extend_theme(config_file, extend) {
+        Ok(changes) => {
+            print_info(&format!("Theme extensions in {}:", config_file.display()));
+            for change in changes {
+                println!("  - {}", change);
+            }
+        }
+        Err(err) => print_warning(&format!(
+            "{:#}\nAdd these to theme.extend in {} by hand:\n{}",
+            err, config_file.display(), manual
+        )),
+    }
+}
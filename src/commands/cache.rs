@@ -0,0 +1,84 @@
+use std::fs;
+use anyhow::Result;
+use colored::*;
+
+use crate::utils::cache::{clean, get_cache_dir, is_digest, load_index, object_path, save_index, short_digest, verify_entry, VerifyStatus};
+use crate::utils::ui::{print_error, print_info, print_success, print_warning};
+
+pub fn list_cache() -> Result<()> {
+    let index = load_index()?;
+
+    if index.entries.is_empty() {
+        print_info("The cache is empty.");
+        return Ok(());
+    }
+
+    println!("{}", "Cached templates".bright_cyan().bold());
+    println!("{}", "----------------".bright_cyan());
+
+    let mut total = 0;
+    for (url, entry) in &index.entries {
+        total += entry.size;
+        println!("{} {}", "•".yellow(), url.bright_white());
+        println!("  {} {}  {} {} bytes", "sha256".cyan(), short_digest(&entry.sha256), "size".cyan(), entry.size);
+    }
+
+    println!("\n{} entries, {} bytes in {}", index.entries.len(), total, get_cache_dir()?.display());
+    Ok(())
+}
+
+pub fn verify_cache() -> Result<()> {
+    let mut index = load_index()?;
+
+    if index.entries.is_empty() {
+        print_info("The cache is empty.");
+        return Ok(());
+    }
+
+    let mut broken = Vec::new();
+    for (url, entry) in &index.entries {
+        match verify_entry(entry)? {
+            VerifyStatus::Ok => println!("{} {}", "ok".green(), url),
+            VerifyStatus::Missing => {
+                println!("{} {}", "missing".red(), url);
+                broken.push(url.clone());
+            }
+            VerifyStatus::Corrupted(actual) => {
+                println!("{} {} (expected {}, found {})", "corrupted".red(), url, short_digest(&entry.sha256), short_digest(&actual));
+                broken.push(url.clone());
+            }
+            VerifyStatus::Invalid => {
+                println!("{} {} (invalid digest '{}' in the index)", "corrupted".red(), url, entry.sha256);
+                broken.push(url.clone());
+            }
+        }
+    }
+
+    if broken.is_empty() {
+        print_success(&format!("All {} cache entries verified.", index.entries.len()));
+        return Ok(());
+    }
+
+    // drop broken entries so they are fetched again next time, an invalid
+    // digest isn't a path in the cache so there is no object to remove
+    for url in &broken {
+        if let Some(entry) = index.entries.remove(url).filter(|entry| is_digest(&entry.sha256)) {
+            let still_used = index.entries.values().any(|other| other.sha256 == entry.sha256);
+            let object = object_path(&entry.sha256)?;
+            if !still_used && object.exists() {
+                fs::remove_file(&object)?;
+            }
+        }
+    }
+    save_index(&index)?;
+
+    print_error(&format!("{} cache entries failed verification.", broken.len()));
+    print_warning("Broken entries were removed and will be fetched again when online.");
+    Ok(())
+}
+
+pub fn clean_cache() -> Result<()> {
+    let freed = clean()?;
+    print_success(&format!("Cache cleaned, {} bytes freed.", freed));
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use anyhow::Result;
+use colored::*;
+
+use crate::models::component::FileType;
+use crate::utils::config::load_project_config;
+use crate::utils::project::{find_project_root, detect_project_info, get_package_manager, get_target_dir};
+use crate::utils::stylesheet::resolve_global_css;
+use crate::utils::tailwind::{check_content_coverage, ContentCoverage, SCRIPT_FILE_TYPES};
+use crate::utils::template_finder::get_template_locations;
+use crate::utils::ui::print_error;
+
+fn ok(message: &str) {
+    println!("  {} {}", "✓".green(), message);
+}
+
+fn warn(message: &str, problems: &mut usize) {
+    *problems += 1;
+    println!("  {} {}", "!".yellow().bold(), message);
+}
+
+// Check the project is set up the way installed components expect, without changing anything
+pub fn run_doctor() -> Result<()> {
+    let project_root = match find_project_root() {
+        Some(root) => root,
+        None => {
+            print_error("No Next.js project found in the current directory or its parents!");
+            return Ok(());
+        }
+    };
+
+    let project_info = detect_project_info(&project_root);
+    let project_config = load_project_config(&project_root)?;
+    let mut problems = 0;
+
+    println!("{} {}", "Checking".bright_cyan().bold(), project_root.display());
+
+    ok(&format!("Next.js project, using {}", get_package_manager(&project_root)));
+
+    match (project_info.app_router, project_info.pages_router) {
+        (true, true) => ok("App and Pages routers"),
+        (true, false) => ok("App router"),
+        (false, true) => ok("Pages router"),
+        (false, false) => warn("No app/ or pages/ directory found", &mut problems),
+    }
+
+    if get_template_locations().is_empty() {
+        warn("No templates found", &mut problems);
+    } else {
+        ok("Templates found");
+    }
+
+    let ui_dir = get_target_dir(&project_root, &project_info, &project_config, FileType::Component);
+    if ui_dir.is_dir() {
+        ok(&format!("Components directory {}", ui_dir.display()));
+    } else {
+        warn(&format!("Components directory {} is missing, run 'vin-ui init'", ui_dir.display()), &mut problems);
+    }
+
+    let utils_file = get_target_dir(&project_root, &project_info, &project_config, FileType::Lib).join("utils.ts");
+    if utils_file.is_file() {
+        ok(&format!("Utilities in {}", utils_file.display()));
+    } else {
+        warn(&format!("{} is missing, run 'vin-ui init'", utils_file.display()), &mut problems);
+    }
+
+    match (&project_info.tailwind, project_info.tailwind_version()) {
+        (Some(tailwind), Some(version)) => match &tailwind.prefix {
+            Some(prefix) => ok(&format!("Tailwind CSS v{}, class prefix '{}'", version, prefix)),
+            None => ok(&format!("Tailwind CSS v{}", version)),
+        },
+        (Some(_), None) => warn("Tailwind CSS found, but not which version", &mut problems),
+        (None, _) => warn("Tailwind CSS not detected, components are styled with Tailwind", &mut problems),
+    }
+
+    match resolve_global_css(&project_root, &project_config) {
+        Some(stylesheet) if stylesheet.is_file() => ok(&format!("Global stylesheet {}", stylesheet.display())),
+        Some(stylesheet) => warn(&format!("Global stylesheet {} not found", stylesheet.display()), &mut problems),
+        None => warn("No global stylesheet found", &mut problems),
+    }
+
+    // every dir scripts are installed to, the ui dir even before anything is in it
+    let script_dirs: BTreeSet<PathBuf> = SCRIPT_FILE_TYPES.iter()
+        .map(|file_type| get_target_dir(&project_root, &project_info, &project_config, *file_type))
+        .filter(|dir| *dir == ui_dir || dir.is_dir())
+        .collect();
+    for dir in &script_dirs {
+        match check_content_coverage(&project_root, &project_info, dir) {
+            ContentCoverage::Covered => ok(&format!("Tailwind content globs include {}", dir.display())),
+            ContentCoverage::Missing(glob) => warn(
+                &format!("Tailwind content globs don't include {}, add \"{}\"", dir.display(), glob),
+                &mut problems,
+            ),
+            ContentCoverage::Unknown(reason) => {
+                warn(&format!("Couldn't check the Tailwind content globs: {}", reason), &mut problems);
+                break;
+            }
+            ContentCoverage::NotApplicable => break,
+        }
+    }
+
+    println!();
+    if problems == 0 {
+        println!("{} Everything looks good!", "SUCCESS:".green().bold());
+    } else {
+        println!("{} {} problem(s) found", "WARNING:".yellow().bold(), problems);
+    }
+    Ok(())
+}
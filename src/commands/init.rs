@@ -2,8 +2,11 @@ use std::fs;
 use anyhow::Result;
 use colored::*;
 
-use crate::utils::project::{find_project_root, ensure_dir};
-use crate::utils::ui::{print_error, print_success, print_info};
+use crate::models::component::FileType;
+use crate::utils::config::load_project_config;
+use crate::utils::project::{find_project_root, ensure_dir, detect_project_info, get_target_dir};
+use crate::utils::tailwind::offer_content_glob;
+use crate::utils::ui::{print_error, print_success, print_info, print_warning};
 
 pub fn init_components_directory() -> Result<()> {
     //find next.js root
@@ -15,16 +18,14 @@ pub fn init_components_directory() -> Result<()> {
         }
     };
 
-    let components_dir = project_root.join("components");
-    let ui_dir = components_dir.join("ui");
-
-    if !components_dir.exists() {
-        ensure_dir(&components_dir)?;
-        print_success(&format!(
-            "Created components directory at {}",
-            components_dir.display()
-        ));
+    let project_info = detect_project_info(&project_root);
+    match project_info.tailwind_version() {
+        Some(version) => print_info(&format!("Detected Tailwind CSS v{}", version)),
+        None if project_info.tailwind.is_some() => print_info("Detected Tailwind CSS"),
+        None => print_warning("Tailwind CSS was not detected, vin-ui components are styled with Tailwind"),
     }
+    let project_config = load_project_config(&project_root)?;
+    let ui_dir = get_target_dir(&project_root, &project_info, &project_config, FileType::Component);
 
     if !ui_dir.exists() {
         ensure_dir(&ui_dir)?;
@@ -40,8 +41,15 @@ pub fn init_components_directory() -> Result<()> {
     }
 
 
+    // components and the utils next to them are only styled if Tailwind scans them
+    let lib_dir = get_target_dir(&project_root, &project_info, &project_config, FileType::Lib);
+    offer_content_glob(&project_root, &project_info, &ui_dir)?;
+    if lib_dir != ui_dir {
+        offer_content_glob(&project_root, &project_info, &lib_dir)?;
+    }
+
+
     // crate /lib if doz not exists
-    let lib_dir = project_root.join("lib");
     if !lib_dir.exists() {
         let _ = ensure_dir(&lib_dir);
 
@@ -1,22 +1,55 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
 use anyhow::Result;
 use colored::*;
 
-use crate::utils::project::get_cli_root;
-use crate::utils::component::{get_available_components, load_component_config};
+use crate::models::component::ItemKind;
+use crate::utils::dependencies::DependencySet;
+use crate::utils::block::{get_available_blocks, load_block_config};
+use crate::utils::hook::{get_available_hooks, load_hook_config};
+use crate::utils::template_finder::get_template_locations;
+use crate::utils::component::{component_config_file, get_available_components, load_component_config};
 use crate::utils::ui::print_info;
 
-pub fn list_components() -> Result<()> {
-    let cli_root = get_cli_root();
-    let template_dir = cli_root.join("templates");
+pub fn list_components(kind: Option<ItemKind>) -> Result<()> {
+    let locations = get_template_locations();
 
-    if !template_dir.exists() {
-        println!("cli:{}, temp:{}", cli_root.display(), template_dir.display());
+    if locations.is_empty() {
         println!("{} Templates directory not found!", "ERROR".red().bold());
         return Ok(());
     }
 
-    // get all availble components
-    let components = get_available_components(&template_dir)?;
+    let template_dirs: Vec<PathBuf> = locations.into_iter().map(|location| location.path).collect();
+    match kind {
+        Some(ItemKind::Component) => list_only_components(&template_dirs),
+        Some(ItemKind::Hook) => list_hooks(&template_dirs),
+        Some(ItemKind::Block) => list_blocks(&template_dirs),
+        None => {
+            list_only_components(&template_dirs)?;
+
+            // the other kinds only show up when there are some
+            if template_dirs.iter().any(|dir| !get_available_hooks(dir).unwrap_or_default().is_empty()) {
+                println!();
+                list_hooks(&template_dirs)?;
+            }
+            if template_dirs.iter().any(|dir| !get_available_blocks(dir).unwrap_or_default().is_empty()) {
+                println!();
+                list_blocks(&template_dirs)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+
+fn list_only_components(template_dirs: &[PathBuf]) -> Result<()> {
+    // get all availble components, earlier locations shadow later ones
+    let mut components: BTreeMap<String, PathBuf> = BTreeMap::new();
+    for template_dir in template_dirs {
+        for component in get_available_components(template_dir)? {
+            components.entry(component).or_insert_with(|| template_dir.clone());
+        }
+    }
 
     if components.is_empty() {
         print_info("No components found in this template.");
@@ -26,24 +59,27 @@ pub fn list_components() -> Result<()> {
     println!("{}", "Available components".bright_cyan().bold());
     println!("{}", "--------------------".bright_cyan());
 
-    for component in components {
+    for (component, template_dir) in components {
         //check for config files
-        let config_path = template_dir.join(format!("{}.json", component));
+        let config_path = template_dir.join(component_config_file(&template_dir, &component));
         if let Some(config) = load_component_config(&config_path)? {
-            let deps = config.dependencies.unwrap_or_default();
+            let deps = DependencySet::of(&config);
             let utils = config.utils.unwrap_or_default();
+            let hooks = config.hooks.unwrap_or_default();
 
-            if !deps.is_empty() || !utils.is_empty() {
+            if !deps.is_empty() || !utils.is_empty() || !hooks.is_empty() {
                 println!("{} {}", "•".yellow(), component.bright_white().bold());
 
-                if !deps.is_empty() {
-                    println!("  {} {}", "Dependencies".cyan(), deps.join(", "));
-                }
+                print_dependencies(&deps);
 
                 if !utils.is_empty() {
                     println!("  {} {}", "Utils".magenta(), utils.join(", "));
                 }
 
+                if !hooks.is_empty() {
+                    println!("  {} {}", "Hooks".blue(), hooks.join(", "));
+                }
+
                 println!();
                 continue;
             }
@@ -54,5 +90,83 @@ pub fn list_components() -> Result<()> {
     }
 
     println!("\nTo add a component, run: {} {}", "vin-ui add".bright_white(), "<component-name>".bright_green());
+
+    Ok(())
+}
+
+
+fn list_hooks(template_dirs: &[PathBuf]) -> Result<()> {
+    let mut hooks: BTreeMap<String, PathBuf> = BTreeMap::new();
+    for template_dir in template_dirs {
+        for hook in get_available_hooks(template_dir)? {
+            hooks.entry(hook).or_insert_with(|| template_dir.clone());
+        }
+    }
+
+    if hooks.is_empty() {
+        print_info("No hooks found in this template.");
+        return Ok(());
+    }
+
+    println!("{}", "Available hooks".bright_cyan().bold());
+    println!("{}", "---------------".bright_cyan());
+
+    for (hook, template_dir) in hooks {
+        let deps = load_hook_config(&template_dir, &hook)?
+            .map(|config| DependencySet::of(&config))
+            .unwrap_or_default();
+
+        println!("{} {}", "•".yellow(), hook);
+        print_dependencies(&deps);
+    }
+
+    println!("\nTo add a hook, run: {} {}", "vin-ui add".bright_white(), "<hook-name>".bright_green());
+    Ok(())
+}
+
+
+fn print_dependencies(deps: &DependencySet) {
+    if !deps.dependencies.is_empty() {
+        println!("  {} {}", "Dependencies".cyan(), deps.dependencies.join(", "));
+    }
+    if !deps.dev_dependencies.is_empty() {
+        println!("  {} {}", "Dev dependencies".cyan(), deps.dev_dependencies.join(", "));
+    }
+    if !deps.peer_dependencies.is_empty() {
+        println!("  {} {}", "Peer dependencies".cyan(), deps.peer_dependencies.join(", "));
+    }
+}
+
+
+fn list_blocks(template_dirs: &[PathBuf]) -> Result<()> {
+    let mut blocks: BTreeMap<String, PathBuf> = BTreeMap::new();
+    for template_dir in template_dirs {
+        for block in get_available_blocks(template_dir)? {
+            blocks.entry(block).or_insert_with(|| template_dir.clone());
+        }
+    }
+
+    if blocks.is_empty() {
+        print_info("No blocks found in this template.");
+        return Ok(());
+    }
+
+    println!("{}", "Available blocks".bright_cyan().bold());
+    println!("{}", "----------------".bright_cyan());
+
+    for (block, template_dir) in blocks {
+        let config = load_block_config(&template_dir, &block)?;
+        let components = config.components.unwrap_or_default();
+
+        println!("{} {}", "•".yellow(), block);
+        if let Some(description) = config.description {
+            println!("  {}", description);
+        }
+        if !components.is_empty() {
+            println!("  {} {}", "Components".green(), components.join(", "));
+        }
+    }
+
+    println!("\nTo add a block, run: {} {}", "vin-ui add".bright_white(), "<block-name> [route]".bright_green());
     Ok(())
 }
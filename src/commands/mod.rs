@@ -1,3 +1,11 @@
 pub mod add;
 pub mod list;
 pub mod init;
+pub mod cache;
+pub mod registry;
+pub mod pack;
+pub mod templates;
+pub mod which;
+pub mod remove;
+pub mod doctor;
+pub mod theme;
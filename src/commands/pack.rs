@@ -0,0 +1,36 @@
+use std::path::{Path, PathBuf};
+use anyhow::Result;
+
+use crate::utils::component::component_entry_file;
+use crate::utils::package::{create_package, PACKAGE_EXTENSION};
+use crate::utils::template_finder::{find_component_dir, get_templates_dir};
+use crate::utils::ui::{print_error, print_success};
+
+pub fn pack_component(component_name: &str, output: Option<&Path>) -> Result<()> {
+    let template_dir = find_component_dir(component_name).unwrap_or_else(get_templates_dir);
+
+    let component_path = template_dir.join(component_entry_file(&template_dir, component_name));
+    if !component_path.exists() {
+        print_error(&format!("Component '{}' not found in templates!", component_name));
+        println!("Run 'vin-ui list' to see available components");
+        return Ok(());
+    }
+
+    let output = output
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from(format!("{}.{}", component_name, PACKAGE_EXTENSION)));
+
+    let manifest = create_package(&template_dir, component_name, &output)?;
+
+    for file in &manifest.files {
+        println!("  {}", file.path);
+    }
+
+    print_success(&format!(
+        "Packed '{}' ({} files) into {}",
+        component_name,
+        manifest.files.len(),
+        output.display()
+    ));
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use std::fs;
+use std::path::Path;
+use anyhow::{Result, Context};
+use colored::*;
+
+use crate::utils::config::load_user_config;
+use crate::utils::integrity::{verify_digest, INDEX_FILE, SIGNATURE_FILE};
+use crate::utils::project::ensure_dir;
+use crate::utils::registry::{fetch_registry_index, filter_entries, safe_join};
+use crate::utils::remote::fetch;
+use crate::utils::ui::{print_info, print_success, print_warning};
+
+// Snapshot a remote registry into a local templates directory
+pub fn mirror_registry(url: &str, dest: &Path, names: &[String], tags: &[String], offline: bool) -> Result<()> {
+    let user_config = load_user_config()?;
+
+    print_info(&format!("Fetching registry index from {}", url));
+    let registry = fetch_registry_index(url, offline, &user_config)
+        .context("Refusing to mirror registry: index verification failed")?;
+
+    if let Some(key) = &registry.signed_by {
+        print_info(&format!("Registry index signed by trusted key '{}'", key));
+    }
+
+    let entries = filter_entries(&registry.index, names, tags);
+    if entries.is_empty() {
+        print_warning("No components in the registry match the given filters.");
+        return Ok(());
+    }
+
+    ensure_dir(dest)?;
+
+    for entry in &entries {
+        println!("{} {}", "•".yellow(), entry.name.bright_white().bold());
+
+        for file in &entry.files {
+            let dest_path = safe_join(dest, &file.path)?;
+            let bytes = fetch(&format!("{}/{}", registry.base_url, file.path), offline)?;
+
+            // never write a file that doesn't match the index
+            if let Some(expected) = &file.sha256 {
+                verify_digest(&file.path, &bytes, expected)
+                    .context("Refusing to mirror registry: file verification failed")?;
+            }
+
+            if let Some(parent) = dest_path.parent() {
+                ensure_dir(parent)?;
+            }
+            fs::write(&dest_path, bytes)
+                .context(format!("Failed to write {}", dest_path.display()))?;
+            println!("  {}", file.path);
+        }
+    }
+
+    // keep the original index (and its signature) so installs from the mirror
+    // can be verified the same way as the registry itself
+    fs::write(dest.join(INDEX_FILE), &registry.index_bytes)?;
+    if let Some(signature) = &registry.signature {
+        fs::write(dest.join(SIGNATURE_FILE), signature)?;
+    }
+
+    print_success(&format!(
+        "Mirrored {} components to {}",
+        entries.len(),
+        dest.display()
+    ));
+    println!(
+        "Use it as a template source with: {}",
+        format!("VIN_UI_TEMPLATES={} vin-ui list", dest.display()).bright_cyan()
+    );
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use std::fs;
+use anyhow::{Result, Context};
+use colored::*;
+
+use crate::models::component::FileType;
+use crate::utils::config::load_project_config;
+use crate::utils::project::{find_project_root, detect_project_info, get_target_dir};
+use crate::utils::stylesheet::{resolve_global_css, strip_component_css};
+use crate::utils::ui::{print_error, print_info, print_success, confirm};
+
+// Remove an installed component along with its assets and the styles it added.
+// Hooks and utilities are shared between components, so they stay.
+pub fn remove_component(component_name: &str) -> Result<()> {
+    let project_root = match find_project_root() {
+        Some(root) => root,
+        None => {
+            print_error("No Next.js project found in the current directory or its parent.");
+            return Ok(());
+        }
+    };
+
+    let project_info = detect_project_info(&project_root);
+    let project_config = load_project_config(&project_root)?;
+    let ui_dir = get_target_dir(&project_root, &project_info, &project_config, FileType::Component);
+    let assets_dir = get_target_dir(&project_root, &project_info, &project_config, FileType::Asset).join(component_name);
+
+    // stylesheets of a style other than Tailwind sit next to the component, e.g. button.module.css
+    let stylesheets = fs::read_dir(&ui_dir).into_iter().flatten().flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.file_name().and_then(|name| name.to_str()).is_some_and(|name| {
+            name.starts_with(&format!("{}.", component_name)) && name.ends_with(".css")
+        }));
+
+    let mut installed: Vec<_> = [ui_dir.join(format!("{}.tsx", component_name)), ui_dir.join(component_name), assets_dir]
+        .into_iter()
+        .filter(|path| path.exists())
+        .collect();
+    installed.extend(stylesheets);
+    let stylesheet = resolve_global_css(&project_root, &project_config).filter(|path| path.is_file());
+
+    if installed.is_empty() && stylesheet.is_none() {
+        print_error(&format!("Component '{}' is not installed in {}", component_name, ui_dir.display()));
+        return Ok(());
+    }
+
+    if !installed.is_empty() {
+        print_info("This will remove:");
+        for path in &installed {
+            println!("  - {}", path.display());
+        }
+
+        if !confirm(&format!("Remove component {}?", component_name), false) {
+            print_info("Operation Cancelled.");
+            return Ok(());
+        }
+    }
+
+    for path in &installed {
+        if path.is_dir() {
+            fs::remove_dir_all(path)
+        } else {
+            fs::remove_file(path)
+        }
+        .context(format!("Failed to remove {}", path.display()))?;
+    }
+
+    if let Some(stylesheet) = stylesheet {
+        if strip_component_css(&stylesheet, component_name)? {
+            print_success(&format!("Removed the styles '{}' added to {}", component_name, stylesheet.display()));
+        } else if installed.is_empty() {
+            print_error(&format!("Component '{}' is not installed in {}", component_name, ui_dir.display()));
+            return Ok(());
+        }
+    }
+
+    println!("{} Component '{}' removed", "SUCCESS:".green().bold(), component_name);
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use anyhow::Result;
+use colored::*;
+
+use crate::utils::template_finder::{get_candidate_locations, get_config_templates_dir};
+use crate::utils::template_store::{
+    get_template_source, install_templates, reset_templates, store_status, update_templates, FileState,
+};
+use crate::utils::ui::{confirm, print_info, print_success, print_warning};
+
+pub fn show_templates_path() -> Result<()> {
+    let candidates = get_candidate_locations();
+    let active = candidates.iter().find(|location| location.path.is_dir());
+
+    match active {
+        Some(location) => {
+            println!("{}", location.path.display().to_string().bright_white().bold());
+            println!("  {} {}", "from".cyan(), location.kind.describe());
+        }
+        None => print_warning("No templates directory found."),
+    }
+
+    println!("\n{}", "Searched, in order:".bright_cyan());
+    for location in &candidates {
+        let marker = if !location.path.is_dir() {
+            "✗".red()
+        } else if active.is_some_and(|active| active.path == location.path) {
+            "→".green()
+        } else {
+            "✓".yellow()
+        };
+        println!("  {} {} ({})", marker, location.path.display(), location.kind.describe());
+    }
+
+    Ok(())
+}
+
+pub fn install() -> Result<()> {
+    match install_templates()? {
+        Some(store) => print_success(&format!("Installed templates to {}", store.display())),
+        None => {
+            print_info(&format!("Templates are already installed at {}", get_config_templates_dir()?.display()));
+            println!("Run {} to sync them.", "vin-ui templates update".bright_cyan());
+        }
+    }
+    Ok(())
+}
+
+pub fn update(force: bool) -> Result<()> {
+    let store = get_config_templates_dir()?;
+    if !store.exists() {
+        return install();
+    }
+
+    let report = update_templates(force)?;
+
+    for file in &report.updated {
+        println!("  {} {}", "updated".green(), file);
+    }
+    for (file, state) in &report.kept {
+        println!("  {} {}", format!("kept {}", state.label()).yellow(), file);
+    }
+
+    if report.updated.is_empty() {
+        print_info("Templates are already up to date.");
+    } else {
+        print_success(&format!("Updated {} template files in {}", report.updated.len(), store.display()));
+    }
+
+    if report.kept.iter().any(|(_, state)| *state == FileState::Modified) {
+        println!("Locally modified files were kept, use {} to overwrite them.", "--force".bright_cyan());
+    }
+    Ok(())
+}
+
+pub fn reset() -> Result<()> {
+    let store = get_config_templates_dir()?;
+
+    if store.exists() && !confirm(&format!("Discard all changes in {} and reinstall?", store.display()), false) {
+        print_info("Operation Cancelled.");
+        return Ok(());
+    }
+
+    let store = reset_templates()?;
+    print_success(&format!("Reset templates in {}", store.display()));
+    Ok(())
+}
+
+pub fn status() -> Result<()> {
+    let store = get_config_templates_dir()?;
+    let source = get_template_source()?;
+
+    println!("{} {}", "Store: ".cyan(), store.display());
+    println!("{} {} ({})", "Source:".cyan(), source.path.display(), source.kind.describe());
+    println!();
+
+    if !store.exists() {
+        print_info("Templates are not installed in the config directory.");
+        println!("Run {} to install them.", "vin-ui templates install".bright_cyan());
+        return Ok(());
+    }
+
+    let states = store_status(&store, &source.path)?;
+    let changed: Vec<_> = states.iter()
+        .filter(|(_, state)| *state != FileState::UpToDate)
+        .collect();
+
+    if changed.is_empty() {
+        print_success(&format!("All {} template files are up to date.", states.len()));
+        return Ok(());
+    }
+
+    for (file, state) in &changed {
+        let label = match state {
+            FileState::Modified | FileState::Extra => state.label().yellow(),
+            _ => state.label().red(),
+        };
+        println!("  {:<12} {}", label, file);
+    }
+
+    println!("\n{} of {} template files differ from the source.", changed.len(), states.len());
+    println!("Run {} to sync them.", "vin-ui templates update".bright_cyan());
+    Ok(())
+}
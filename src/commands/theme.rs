@@ -0,0 +1,94 @@
+use std::collections::BTreeMap;
+use anyhow::{Result, bail};
+use colored::*;
+use serde_json::Value;
+
+use crate::models::theme::Theme;
+use crate::utils::config::{load_project_config, set_project_config_value, PROJECT_CONFIG_FILE};
+use crate::utils::project::find_project_root;
+use crate::utils::stylesheet::resolve_global_css;
+use crate::utils::template_finder::get_template_locations;
+use crate::utils::theme::{get_available_themes, load_template_theme, load_theme, merge_theme_css, theme_var};
+use crate::utils::ui::{print_error, print_info, print_success};
+
+pub fn list_themes() -> Result<()> {
+    let project_config = find_project_root()
+        .map(|root| load_project_config(&root))
+        .transpose()?
+        .unwrap_or_default();
+
+    // the project's own themes shadow template themes with the same name
+    let mut themes: BTreeMap<String, Theme> = project_config.themes.clone();
+    for location in get_template_locations() {
+        for theme_name in get_available_themes(&location.path)? {
+            if themes.contains_key(&theme_name) {
+                continue;
+            }
+            if let Ok(theme) = load_template_theme(&location.path, &theme_name) {
+                themes.insert(theme_name, theme);
+            }
+        }
+    }
+
+    if themes.is_empty() {
+        print_info("No themes found.");
+        return Ok(());
+    }
+
+    println!("{}", "Available themes".bright_cyan().bold());
+    println!("{}", "----------------".bright_cyan());
+
+    for (theme_name, theme) in &themes {
+        if project_config.theme.as_ref() == Some(theme_name) {
+            println!("{} {} {}", "•".yellow(), theme_name.bright_white().bold(), "(current)".green());
+        } else {
+            println!("{} {}", "•".yellow(), theme_name);
+        }
+        if let Some(description) = &theme.description {
+            println!("  {}", description);
+        }
+        println!("  {} {}", "Colors".cyan(), theme.colors.keys().cloned().collect::<Vec<_>>().join(", "));
+    }
+
+    println!("\nTo use a theme, run: {} {}", "vin-ui theme set".bright_white(), "<theme-name>".bright_green());
+    Ok(())
+}
+
+// Write the theme's colors to the global stylesheet and make it the project's theme
+pub fn set_theme(theme_name: &str) -> Result<()> {
+    let project_root = match find_project_root() {
+        Some(root) => root,
+        None => {
+            print_error("No Next.js project found in the current directory or its parent.");
+            return Ok(());
+        }
+    };
+
+    let project_config = load_project_config(&project_root)?;
+    let theme = load_theme(&project_config, theme_name)?;
+
+    let stylesheet = match resolve_global_css(&project_root, &project_config) {
+        Some(stylesheet) if stylesheet.is_file() => stylesheet,
+        Some(stylesheet) => bail!("Global stylesheet {} not found, the theme's colors go there", stylesheet.display()),
+        None => bail!("No global stylesheet found, set \"css\" in {} for the theme's colors", PROJECT_CONFIG_FILE),
+    };
+
+    if merge_theme_css(&stylesheet, &theme)? {
+        print_success(&format!("Added the colors of theme '{}' to {}", theme_name, stylesheet.display()));
+    }
+    for color in theme.colors.keys() {
+        println!("  {}", theme_var(color));
+    }
+
+    if project_config.theme.as_deref() != Some(theme_name) {
+        let previous = project_config.theme.as_deref();
+        set_project_config_value(&project_root, "theme", Value::String(theme_name.to_string()))?;
+        print_success(&format!("Theme '{}' set in {}", theme_name, PROJECT_CONFIG_FILE));
+
+        // components already installed keep pointing at the same variables
+        if previous.is_none() {
+            print_info("Components added before setting a theme keep their own colors, add them again to use the theme");
+        }
+    }
+    Ok(())
+}
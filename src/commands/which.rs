@@ -0,0 +1,189 @@
+use std::fs;
+use std::path::Path;
+use anyhow::Result;
+use colored::*;
+
+use crate::models::registry::RegistryIndex;
+use crate::utils::cache::{load_index, lookup, short_digest};
+use crate::utils::component::{component_config_file, component_entry_file};
+use crate::utils::config::load_user_config;
+use crate::utils::integrity::INDEX_FILE;
+use crate::utils::overlay::overlay_files_for;
+use crate::utils::package::{is_package, read_package_manifest};
+use crate::utils::registry::split_registry_url;
+use crate::utils::template_finder::{find_ancestor_templates, get_candidate_locations};
+use crate::utils::ui::print_warning;
+
+fn print_copy(marker: ColoredString, dir: &Path, component_name: &str, source: &str, status: &str) {
+    let component_path = dir.join(component_entry_file(dir, component_name));
+    let config_path = dir.join(component_config_file(dir, component_name));
+
+    println!("  {} {} ({}) {}", marker, component_path.display(), source, status);
+    if config_path.exists() {
+        println!("      {} {}", "config".cyan(), config_path.display());
+    } else {
+        println!("      {} none", "config".cyan());
+    }
+}
+
+// Whether a registry index lists the component
+fn index_lists(index_bytes: &[u8], component_name: &str) -> bool {
+    serde_json::from_slice::<RegistryIndex>(index_bytes)
+        .is_ok_and(|index| index.components.iter().any(|entry| entry.name == component_name))
+}
+
+// A templates dir with an index.json listing the component was mirrored from a
+// registry (or ships one like a registry does)
+fn describe_location(dir: &Path, kind: &str, component_name: &str) -> String {
+    match fs::read(dir.join(INDEX_FILE)) {
+        Ok(bytes) if index_lists(&bytes, component_name) => format!("{}, registry mirror", kind),
+        _ => kind.to_string(),
+    }
+}
+
+// Component a cached URL belongs to, going by its file name: <name>.tsx or
+// <name>.json, at the root or in a <name>/ directory
+fn cached_component_name(url: &str) -> Option<&str> {
+    let (_, file_name) = url.rsplit_once('/')?;
+    if file_name == INDEX_FILE {
+        return None;
+    }
+    file_name.strip_suffix(".tsx").or_else(|| file_name.strip_suffix(".json"))
+}
+
+// Show every copy of a component across the template locations and which one wins
+pub fn which_component(component_name: &str) -> Result<()> {
+    let candidates = get_candidate_locations();
+    let mut winner_found = false;
+    let mut copies = 0;
+
+    println!("{}", component_name.bright_white().bold());
+
+    for location in &candidates {
+        let dir = &location.path;
+        if !dir.join(component_entry_file(dir, component_name)).exists() {
+            if dir.join(format!("{}.json", component_name)).exists() {
+                println!(
+                    "  {} {} ({}) {}",
+                    "!".yellow(),
+                    dir.join(format!("{}.json", component_name)).display(),
+                    location.kind.describe(),
+                    "config without a component, ignored".yellow()
+                );
+            }
+            continue;
+        }
+
+        copies += 1;
+        let source = describe_location(dir, location.kind.describe(), component_name);
+        if winner_found {
+            print_copy("✓".yellow(), dir, component_name, &source, &"shadowed".yellow());
+        } else {
+            winner_found = true;
+            print_copy("→".green(), dir, component_name, &source, &"wins".green().bold());
+        }
+    }
+
+    // templates/ further up than the nearest one are never searched
+    for dir in find_ancestor_templates().iter().skip(1) {
+        if dir.join(component_entry_file(dir, component_name)).exists() {
+            copies += 1;
+            print_copy("·".dimmed(), dir, component_name, "templates/ in a parent directory", &"not searched".dimmed());
+        }
+    }
+
+    let overlay_files = overlay_files_for(component_name, &load_user_config()?.overlays);
+    if !overlay_files.is_empty() {
+        println!("\n{}", "Overlays applied on top of the winning copy:".bright_cyan());
+        for file in overlay_files {
+            println!("  {} {}", "•".yellow(), file.display());
+        }
+    }
+
+    // remote copies are only used when added by URL, but may explain a difference
+    let index = load_index()?;
+    let mut remote = Vec::new();
+    let mut registries = Vec::new();
+    for (url, entry) in &index.entries {
+        if cached_component_name(url) == Some(component_name) {
+            remote.push((url, entry));
+        } else if url.ends_with(&format!("/{}", INDEX_FILE)) {
+            if let Some(bytes) = lookup(url)? {
+                if index_lists(&bytes, component_name) {
+                    registries.push(split_registry_url(url).0);
+                }
+            }
+        }
+    }
+
+    if !registries.is_empty() {
+        println!("\n{}", "Registries listing it (cached index, used by 'registry mirror'):".bright_cyan());
+        for base_url in registries {
+            println!("  {} {} (registry)", "•".yellow(), base_url);
+        }
+    }
+
+    if !remote.is_empty() {
+        println!("\n{}", "Cached remote URLs (used only when added by URL):".bright_cyan());
+        for (url, entry) in remote {
+            println!("  {} {} (cached remote URL) {} {}", "•".yellow(), url, "sha256".cyan(), short_digest(&entry.sha256));
+        }
+    }
+
+    // packages aren't unpacked anywhere that lasts, so look for the archives themselves
+    let mut packages = Vec::new();
+    for entry in fs::read_dir(".")?.flatten() {
+        let path = entry.path();
+        if !is_package(&path.to_string_lossy()) {
+            continue;
+        }
+        match read_package_manifest(&path) {
+            Ok(manifest) if manifest.component == component_name => packages.push(path),
+            Ok(_) => {}
+            Err(e) => print_warning(&format!("Skipping {}: {}", path.display(), e)),
+        }
+    }
+    packages.sort();
+
+    if !packages.is_empty() {
+        println!("\n{}", "Packages in the current directory (used only when added from the file):".bright_cyan());
+        for path in packages {
+            println!("  {} {} (.vinpkg package)", "•".yellow(), path.display());
+        }
+    }
+
+    if copies == 0 {
+        print_warning(&format!("Component '{}' was not found in any template location.", component_name));
+        println!("Run 'vin-ui list' to see available components");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cached_urls_are_matched_by_component_name() {
+        let cases = [
+            ("https://example.com/ui/button.tsx", Some("button")),
+            ("https://example.com/ui/button/button.tsx", Some("button")),
+            ("https://example.com/ui/button.json", Some("button")),
+            ("https://example.com/ui/icon-button.tsx", Some("icon-button")),
+            ("https://example.com/ui/index.json", None),
+            ("https://example.com/ui/button.css", None),
+        ];
+        for (url, expected) in cases {
+            assert_eq!(cached_component_name(url), expected, "{}", url);
+        }
+    }
+
+    #[test]
+    fn index_lists_only_named_components() {
+        let index = br#"{ "name": "ui", "components": [{ "name": "button", "files": [] }] }"#;
+        assert!(index_lists(index, "button"));
+        assert!(!index_lists(index, "card"));
+        assert!(!index_lists(b"not json", "button"));
+    }
+}
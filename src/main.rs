@@ -3,19 +3,11 @@ mod models;
 mod utils;
 mod cli;
 
-use colored::*;
-use utils::template_finder;
-
-fn main() -> anyhow::Result<()> {
-    // Check if templates exist, install if needed
-    let templates_dir = template_finder::get_templates_dir();
-    if !templates_dir.exists() {
-        println!("{} Templates directory not found.", "INFO:".blue().bold());
-        println!("Installing templates to configuration directory...");
-        template_finder::install_templates()?;
-    }
+use std::path::Path;
 
+use models::component::ItemKind;
 
+fn main() -> anyhow::Result<()> {
     let app = cli::build_cli();
     let matches = app.get_matches();
 
@@ -29,10 +21,65 @@ fn main() -> anyhow::Result<()> {
     match matches.subcommand() {
         Some(("add", add_matches)) => {
             let component_name = add_matches.get_one::<String>("COMPONENT_NAME").unwrap();
-            commands::add::add_component(component_name).expect("Skill issues on adding compo");
+            let route = add_matches.get_one::<String>("ROUTE").map(String::as_str);
+            let style = add_matches.get_one::<String>("style").map(String::as_str);
+            let rtl = add_matches.get_flag("rtl");
+            let theme = add_matches.get_one::<String>("theme").map(String::as_str);
+            let offline = add_matches.get_flag("offline");
+            commands::add::add_item(component_name, route, style, theme, rtl, offline)?;
+        }
+        Some(("pack", pack_matches)) => {
+            let component_name = pack_matches.get_one::<String>("COMPONENT_NAME").unwrap();
+            let output = pack_matches.get_one::<String>("output").map(Path::new);
+            commands::pack::pack_component(component_name, output)?;
+        }
+        Some(("which", which_matches)) => {
+            let component_name = which_matches.get_one::<String>("COMPONENT_NAME").unwrap();
+            commands::which::which_component(component_name)?;
+        }
+        Some(("list", list_matches)) => {
+            let kind = list_matches.get_one::<String>("kind").and_then(|kind| ItemKind::parse(kind));
+            commands::list::list_components(kind)?;
+        }
+        Some(("remove", remove_matches)) => {
+            let component_name = remove_matches.get_one::<String>("COMPONENT_NAME").unwrap();
+            commands::remove::remove_component(component_name)?;
         }
-        Some(("list", _)) => commands::list::list_components()?,
+        Some(("doctor", _)) => commands::doctor::run_doctor()?,
         Some(("init", _)) => commands::init::init_components_directory()?,
+        Some(("theme", theme_matches)) => match theme_matches.subcommand() {
+            Some(("list", _)) => commands::theme::list_themes()?,
+            Some(("set", set_matches)) => {
+                let theme_name = set_matches.get_one::<String>("THEME_NAME").unwrap();
+                commands::theme::set_theme(theme_name)?;
+            }
+            _ => cli::print_help(),
+        },
+        Some(("cache", cache_matches)) => match cache_matches.subcommand() {
+            Some(("ls", _)) => commands::cache::list_cache()?,
+            Some(("verify", _)) => commands::cache::verify_cache()?,
+            Some(("clean", _)) => commands::cache::clean_cache()?,
+            _ => cli::print_help(),
+        },
+        Some(("templates", templates_matches)) => match templates_matches.subcommand() {
+            Some(("path", _)) => commands::templates::show_templates_path()?,
+            Some(("install", _)) => commands::templates::install()?,
+            Some(("update", update_matches)) => commands::templates::update(update_matches.get_flag("force"))?,
+            Some(("reset", _)) => commands::templates::reset()?,
+            Some(("status", _)) => commands::templates::status()?,
+            _ => cli::print_help(),
+        },
+        Some(("registry", registry_matches)) => match registry_matches.subcommand() {
+            Some(("mirror", mirror_matches)) => {
+                let url = mirror_matches.get_one::<String>("URL").unwrap();
+                let dir = mirror_matches.get_one::<String>("DIR").unwrap();
+                let names: Vec<String> = mirror_matches.get_many::<String>("name").unwrap_or_default().cloned().collect();
+                let tags: Vec<String> = mirror_matches.get_many::<String>("tag").unwrap_or_default().cloned().collect();
+                let offline = mirror_matches.get_flag("offline");
+                commands::registry::mirror_registry(url, Path::new(dir), &names, &tags, offline)?;
+            }
+            _ => cli::print_help(),
+        },
         Some(("help", _)) => cli::print_help(), // Explicitly handle help subcommand
         _ => cli::print_help(), // Default case for unrecognized subcommands
     }
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::component::{Dependencies, FileType, TemplateVariable};
+
+// templates/blocks/<name>/<name>.json
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BlockConfig {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub components: Option<Vec<String>>,     // Installed like `vin-ui add <component>`
+    pub dependencies: Option<Dependencies>,
+    pub dev_dependencies: Option<Dependencies>,
+    pub peer_dependencies: Option<Dependencies>,
+    pub variables: Option<Vec<TemplateVariable>>,
+    pub files: Vec<BlockFile>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BlockFile {
+    pub source: String,           // Source file path in the block's directory
+    pub dest: Option<String>,     // Destination relative to the type's dir, {route} is the route name
+    #[serde(rename = "type")]
+    pub file_type: Option<FileType>, // Defaults to page, or asset for images, fonts, ...
+}
@@ -0,0 +1,14 @@
+use std::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct CacheIndex {
+    pub entries: BTreeMap<String, CacheEntry>, // Source URL -> cached payload
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEntry {
+    pub sha256: String,     // Digest of the payload, also its file name under objects/
+    pub size: u64,          // Payload size in bytes
+    pub fetched_at: u64,    // Unix timestamp of the last successful fetch
+}
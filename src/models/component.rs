@@ -1,17 +1,139 @@
+use std::collections::BTreeMap;
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ComponentConfig {
     pub name: Option<String>,
     pub description: Option<String>,
-    pub dependencies: Option<Vec<String>>,
+    pub dependencies: Option<Dependencies>,
+    pub dev_dependencies: Option<Dependencies>,   // Installed with the package manager's dev flag
+    pub peer_dependencies: Option<Dependencies>,  // Checked against the project's package.json, not installed
     pub utils: Option<Vec<String>>,
+    pub hooks: Option<Vec<String>>,      // Hooks from templates/hooks the component uses
     pub additional_files: Option<Vec<AdditionalFile>>,
+    pub variables: Option<Vec<TemplateVariable>>,
+    pub tailwind: Option<TailwindRequirement>,
+    pub css: Option<ComponentCss>,
+    pub tailwind_extend: Option<Map<String, Value>>,  // Added to theme.extend in tailwind.config
+    pub colors: Option<BTreeMap<String, String>>,     // Palette color -> the color the template uses for it
+    pub styles: Option<BTreeMap<String, StyleVariant>>, // Styles other than Tailwind, e.g. "css-modules"
+}
+
+// npm packages, either a list (`"lucide-react@^0.400"` pins a version) or package -> version range
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Dependencies {
+    List(Vec<String>),
+    Ranges(BTreeMap<String, String>),
+}
+
+impl Dependencies {
+    // What the package manager is given, `name` or `name@range`
+    pub fn specs(&self) -> Vec<String> {
+        match self {
+            Dependencies::List(specs) => specs.clone(),
+            Dependencies::Ranges(ranges) => ranges.iter()
+                .map(|(name, range)| match range.trim() {
+                    "" | "*" => name.clone(),
+                    range => format!("{}@{}", name, range),
+                })
+                .collect(),
+        }
+    }
+}
+
+// The Tailwind entry file is the default style, others replace it with their own
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StyleVariant {
+    pub entry: String,                // Installed in place of the entry file
+    pub files: Option<Vec<String>>,   // Stylesheets installed next to it, e.g. button.module.css
+}
+
+// CSS merged into the project's global stylesheet
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ComponentCss {
+    pub vars: Option<BTreeMap<String, BTreeMap<String, String>>>,  // Selector -> custom property -> value
+    pub keyframes: Option<BTreeMap<String, String>>,              // Name -> keyframe rules
+    pub layers: Option<BTreeMap<String, String>>,                 // Layer -> rules, e.g. "components"
+}
+
+// Name of the style the entry file itself is written in
+pub const TAILWIND_STYLE: &str = "tailwind";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TailwindRequirement {
+    pub version: u32,                             // Tailwind major version the template is written for
+    pub variants: Option<BTreeMap<u32, String>>,  // Entry files for other major versions, next to the config
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AdditionalFile {
     pub source: String,           // Source file path in templates/utils
-    pub dest: Option<String>,     // Optional custom destination path (relative to appropriate dir)
+    pub dest: Option<String>,     // Optional custom destination path (relative to the type's dir)
     pub description: Option<String>, // Optional description
+    #[serde(rename = "type")]
+    pub file_type: Option<FileType>, // What the file is, decides where it is installed
+}
+
+// Kinds of files a template can install, each mapped to a project directory
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FileType {
+    Component,
+    Lib,
+    Hook,
+    Style,
+    Asset,
+    Page,
+    ApiRoute,
+    Config,
+}
+
+// Images, fonts and media, served from public/ rather than bundled
+const ASSET_EXTENSIONS: &[&str] = &[
+    "png", "jpg", "jpeg", "gif", "webp", "avif", "svg", "ico",
+    "woff", "woff2", "ttf", "otf", "mp4", "webm", "mp3",
+];
+
+impl FileType {
+    // Fallback for files that don't declare a type, based on the old extension rules
+    pub fn from_extension(path: &str) -> FileType {
+        if path.ends_with(".css") || path.ends_with(".scss") {
+            FileType::Style
+        } else if ASSET_EXTENSIONS.iter().any(|ext| path.to_lowercase().ends_with(&format!(".{}", ext))) {
+            FileType::Asset
+        } else if path.ends_with(".ts") {
+            FileType::Lib
+        } else {
+            FileType::Component
+        }
+    }
+}
+
+// Kinds of items the templates directory provides
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemKind {
+    Component,
+    Hook,
+    Block,
+}
+
+impl ItemKind {
+    pub fn parse(kind: &str) -> Option<ItemKind> {
+        match kind {
+            "component" => Some(ItemKind::Component),
+            "hook" => Some(ItemKind::Hook),
+            "block" => Some(ItemKind::Block),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TemplateVariable {
+    pub name: String,                 // Used as {%= name %} in template files
+    pub description: Option<String>,
+    pub default: Option<String>,
+    pub prompt: Option<bool>,         // Ask for the value during `add` instead of using the default
 }
@@ -0,0 +1,42 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+
+use crate::models::component::FileType;
+use crate::models::theme::Theme;
+
+// User-wide settings, read from <config dir>/vin-ui/config.json
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct UserConfig {
+    #[serde(default)]
+    pub trusted_keys: Vec<TrustedKey>,
+    #[serde(default)]
+    pub require_signatures: bool,   // Refuse templates that are not covered by a signed index
+    #[serde(default)]
+    pub overlays: Vec<PathBuf>,     // Overlay dirs patched onto upstream templates, later ones win
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TrustedKey {
+    pub name: String,
+    pub public_key: String,         // Hex-encoded ed25519 public key
+}
+
+// Per-project settings, read from vin-ui.json in the project root
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ProjectConfig {
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub paths: BTreeMap<FileType, String>,   // File type -> directory, relative to the project root
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub css: Option<String>,                 // Global stylesheet, detected when not set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<String>,               // Style components are added in, Tailwind when not set
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub rtl: bool,                           // Rewrite physical utilities to logical ones for RTL
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icons: Option<String>,               // Icon library from templates/icons.json icons are swapped to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub theme: Option<String>,               // Theme set with `vin-ui theme set`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub themes: BTreeMap<String, Theme>,     // The project's own themes, by name
+}
@@ -0,0 +1,15 @@
+use std::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
+
+// templates/icons.json: icon libraries and the names each gives the same icon
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IconTable {
+    pub libraries: BTreeMap<String, IconLibrary>,
+    pub icons: Vec<BTreeMap<String, String>>,    // Library -> name of the icon there, missing when it has none
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IconLibrary {
+    pub package: String,      // npm package, e.g. @heroicons/react
+    pub import: String,       // Module icons are imported from, e.g. @heroicons/react/24/outline
+}
@@ -1 +1,11 @@
 pub mod component;
+pub mod cache;
+pub mod registry;
+pub mod config;
+pub mod package;
+pub mod store;
+pub mod overlay;
+pub mod project;
+pub mod block;
+pub mod theme;
+pub mod icon;
@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+
+// One entry of a `<file>.overlay.json` patch list
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OverlayPatch {
+    pub find: String,       // Exact text expected in the upstream file
+    pub replace: String,
+}
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::component::ComponentConfig;
+use crate::models::registry::RegistryFile;
+
+pub const PACKAGE_FORMAT_VERSION: u32 = 1;
+
+// manifest.json at the root of a .vinpkg archive
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PackageManifest {
+    pub format_version: u32,
+    pub component: String,              // Component name, i.e. the template file stem
+    pub config: Option<ComponentConfig>,
+    pub files: Vec<RegistryFile>,       // Packed files in the templates layout, with digests
+}
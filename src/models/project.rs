@@ -0,0 +1,27 @@
+use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+
+// Facts about the target project that templates can depend on
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectInfo {
+    pub typescript: bool,
+    pub src_dir: bool,                      // Code lives under src/
+    pub app_router: bool,
+    pub pages_router: bool,
+    pub tailwind: Option<TailwindInfo>,     // None when Tailwind isn't used
+    pub alias: String,                      // Import alias prefix, e.g. "@"
+}
+
+impl ProjectInfo {
+    pub fn tailwind_version(&self) -> Option<u32> {
+        self.tailwind.as_ref().and_then(|tailwind| tailwind.version)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TailwindInfo {
+    pub version: Option<u32>,               // Major version, None when it couldn't be worked out
+    pub config_file: Option<PathBuf>,       // tailwind.config.*
+    pub css_file: Option<PathBuf>,          // Stylesheet loading Tailwind
+    pub prefix: Option<String>,             // Class prefix as written in classes, `tw-` (v3) or `tw:` (v4)
+}
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RegistryIndex {
+    pub name: Option<String>,
+    pub components: Vec<RegistryEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegistryEntry {
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub files: Vec<RegistryFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegistryFile {
+    pub path: String,             // Relative to the registry root, in the templates layout
+    pub sha256: Option<String>,   // Hex digest checked before the file is used
+}
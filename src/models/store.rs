@@ -0,0 +1,9 @@
+use std::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
+
+// Written into the config-dir templates copy to remember what was installed
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct StoreManifest {
+    pub source: String,                     // Where the templates were synced from
+    pub files: BTreeMap<String, String>,    // Relative path -> SHA-256 as installed
+}
@@ -0,0 +1,11 @@
+use std::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
+
+// templates/themes/<name>.json, or an entry of `themes` in vin-ui.json
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Theme {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub colors: BTreeMap<String, String>,          // Palette color -> value, e.g. "primary": "#0ea5e9"
+    pub dark: Option<BTreeMap<String, String>>,    // Values under .dark, for colors that differ
+}
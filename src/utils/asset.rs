@@ -0,0 +1,122 @@
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use anyhow::{Result, Context};
+
+// Assets (images, fonts, ...) are copied as they are into public/<namespace>/,
+// and the installed files referring to them are pointed at their public URL,
+// or at the installed file for imports
+
+pub struct AssetLink {
+    pub source: String,         // Relative to the templates dir
+    pub path: PathBuf,          // Where the asset is installed
+    pub url: Option<String>,    // Where the asset is served from, e.g. /hero/logo.png, None outside public/
+}
+
+// URL of a file installed as dest under assets_dir, with public/ served from the site root.
+// None when assets_dir isn't in public/, nothing is served from there
+pub fn public_url(public_dir: &Path, assets_dir: &Path, dest: &str) -> Option<String> {
+    let namespace = assets_dir.strip_prefix(public_dir).ok()?;
+    let mut parts: Vec<String> = namespace.components()
+        .filter_map(|c| match c {
+            Component::Normal(part) => Some(part.to_string_lossy().to_string()),
+            _ => None,
+        })
+        .collect();
+    parts.push(dest.trim_start_matches('/').to_string());
+    Some(format!("/{}", parts.join("/")))
+}
+
+// Path of `target` as referenced from `from_file`, both relative to the templates dir
+pub fn relative_reference(from_file: &str, target: &str) -> String {
+    let mut from_dir: Vec<&str> = from_file.split('/').collect();
+    from_dir.pop();
+    let target: Vec<&str> = target.split('/').collect();
+
+    let common = from_dir.iter()
+        .zip(&target[..target.len() - 1])
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let prefix = match from_dir.len() - common {
+        0 => "./".to_string(),
+        ups => "../".repeat(ups),
+    };
+    format!("{}{}", prefix, target[common..].join("/"))
+}
+
+// Path of `target` as imported from a file in `from_dir`, both installed paths
+fn import_path(from_dir: &Path, target: &Path) -> String {
+    let from: Vec<Component> = from_dir.components().collect();
+    let target: Vec<Component> = target.components().collect();
+    let common = from.iter().zip(&target).take_while(|(a, b)| a == b).count();
+
+    let mut parts: Vec<String> = vec!["..".to_string(); from.len() - common];
+    if parts.is_empty() {
+        parts.push(".".to_string());
+    }
+    parts.extend(target[common..].iter().map(|part| part.as_os_str().to_string_lossy().to_string()));
+    parts.join("/")
+}
+
+// Imports keep importing the asset, from where it's installed, so
+// `import logo from "./logo.png"` still gives `logo.src`, `logo.width`, ...
+// Everything else gets the public URL.
+pub fn rewrite_asset_references(content: &str, from_file: &str, installed_file: &Path, assets: &[AssetLink]) -> String {
+    let installed_dir = installed_file.parent().unwrap_or(Path::new(""));
+    let mut output = String::with_capacity(content.len());
+
+    for line in content.split_inclusive('\n') {
+        let mut line = line.to_string();
+        let is_import = line.trim_start().starts_with("import ");
+        for asset in assets {
+            let reference = relative_reference(from_file, &asset.source);
+            let replacement = if is_import {
+                import_path(installed_dir, &asset.path)
+            } else {
+                match &asset.url {
+                    Some(url) => url.clone(),
+                    None => continue,
+                }
+            };
+
+            for quote in ['"', '\'', '`'] {
+                line = line.replace(
+                    &format!("{}{}{}", quote, reference, quote),
+                    &format!("{}{}{}", quote, replacement, quote),
+                );
+            }
+            if !is_import {
+                line = line.replace(&format!("url({})", reference), &format!("url({})", replacement));
+            }
+        }
+        output.push_str(&line);
+    }
+
+    output
+}
+
+// Point an installed text file at the public URLs of the assets it uses
+pub fn rewrite_asset_references_in_file(path: &Path, from_file: &str, assets: &[AssetLink]) -> Result<()> {
+    if assets.is_empty() {
+        return Ok(());
+    }
+
+    // binary files have nothing to rewrite
+    let Ok(content) = fs::read_to_string(path) else {
+        return Ok(());
+    };
+
+    let rewritten = rewrite_asset_references(&content, from_file, path, assets);
+    if rewritten != content {
+        fs::write(path, rewritten)
+            .context(format!("Failed to update asset paths in {}", path.display()))?;
+    }
+    Ok(())
+}
+
+// Assets are never rendered, an SVG is copied byte for byte like a PNG
+pub fn install_asset(source: &Path, dest: &Path) -> Result<()> {
+    fs::copy(source, dest)
+        .context(format!("Failed to copy {} to {}", source.display(), dest.display()))?;
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use std::fs;
+use std::path::{Component, Path};
+use anyhow::{Result, Context, bail};
+
+use crate::models::block::{BlockConfig, BlockFile};
+use crate::models::component::FileType;
+use crate::models::project::ProjectInfo;
+
+pub const BLOCKS_DIR: &str = "blocks";
+
+// Blocks live in templates/blocks/<name>/ with a <name>.json listing the
+// components they use and the page/layout files they create for a route
+pub fn is_block(template_dir: &Path, block_name: &str) -> bool {
+    template_dir.join(block_config_file(block_name)).is_file()
+}
+
+// Config file of a block, relative to the templates dir
+pub fn block_config_file(block_name: &str) -> String {
+    format!("{}/{}/{}.json", BLOCKS_DIR, block_name, block_name)
+}
+
+pub fn load_block_config(template_dir: &Path, block_name: &str) -> Result<BlockConfig> {
+    let config_path = template_dir.join(block_config_file(block_name));
+    let config_content = fs::read_to_string(&config_path)
+        .context(format!("Failed to read config file: {}", config_path.display()))?;
+
+    let config: BlockConfig = serde_json::from_str(&config_content)
+        .context(format!("Failed to parse config file: {}", config_path.display()))?;
+
+    // `route` is filled in from the command line, a variable can't take its place
+    if config.variables.iter().flatten().any(|variable| variable.name == "route") {
+        bail!("Block '{}' declares a variable named 'route', which is reserved for the route it's installed at", block_name);
+    }
+    Ok(config)
+}
+
+// Every template file (relative to the templates dir) installing a block reads
+pub fn block_template_files(block_name: &str, config: &BlockConfig) -> Vec<String> {
+    let mut files = vec![block_config_file(block_name)];
+    for file in &config.files {
+        files.push(format!("{}/{}/{}", BLOCKS_DIR, block_name, file.source));
+    }
+    files
+}
+
+// Lists all available blocks in the templates directory
+pub fn get_available_blocks(template_dir: &Path) -> Result<Vec<String>> {
+    let blocks_dir = template_dir.join(BLOCKS_DIR);
+    if !blocks_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut blocks = Vec::new();
+    for entry in fs::read_dir(&blocks_dir)?.flatten() {
+        if let Some(name) = entry.file_name().to_str() {
+            if is_block(template_dir, name) {
+                blocks.push(name.to_string());
+            }
+        }
+    }
+
+    blocks.sort();
+    Ok(blocks)
+}
+
+pub fn is_relative_path(path: &str) -> bool {
+    !path.is_empty() && Path::new(path).components().all(|c| matches!(c, Component::Normal(_)))
+}
+
+// Routes are relative paths like `dashboard`, `admin/reports` or `(marketing)/about`
+pub fn validate_route(route: &str) -> Result<()> {
+    if !is_relative_path(route) {
+        bail!("Invalid route '{}', expected a relative path like 'dashboard' or 'admin/reports'", route);
+    }
+    Ok(())
+}
+
+// Undeclared files are pages, apart from assets
+pub fn block_file_type(file: &BlockFile) -> FileType {
+    match file.file_type {
+        Some(file_type) => file_type,
+        None if FileType::from_extension(&file.source) == FileType::Asset => FileType::Asset,
+        None => FileType::Page,
+    }
+}
+
+// Destination of a block file relative to its type's dir. Pages and API routes
+// go under the route by default; with the Pages router `page.*` becomes
+// `index.*`, and layouts have no equivalent so they are skipped (None).
+pub fn block_file_dest(file: &BlockFile, route: &str, project: &ProjectInfo) -> Option<String> {
+    if let Some(dest) = &file.dest {
+        return Some(dest.replace("{route}", route));
+    }
+
+    let file_name = file.source.rsplit('/').next().unwrap_or(&file.source);
+    match block_file_type(file) {
+        FileType::Page | FileType::ApiRoute => {
+            let pages_router = project.pages_router && !project.app_router;
+            let (stem, ext) = file_name.split_once('.').unwrap_or((file_name, "tsx"));
+
+            if !pages_router {
+                Some(format!("{}/{}", route, file_name))
+            } else if stem == "page" {
+                Some(format!("{}/index.{}", route, ext))
+            } else if stem == "layout" {
+                None
+            } else {
+                Some(format!("{}/{}", route, file_name))
+            }
+        }
+        _ => Some(file.source.clone()),
+    }
+}
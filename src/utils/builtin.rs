@@ -0,0 +1,80 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use anyhow::{Result, Context};
+use include_dir::{include_dir, Dir, File};
+use sha2::{Digest, Sha256};
+
+use crate::utils::cache::get_cache_dir;
+
+// The templates shipped with vin-ui, compiled into the binary
+static BUILTIN_TEMPLATES: Dir = include_dir!("$CARGO_MANIFEST_DIR/templates");
+
+fn collect_files<'a>(dir: &'a Dir<'a>, files: &mut Vec<&'a File<'a>>) {
+    files.extend(dir.files());
+    for sub_dir in dir.dirs() {
+        collect_files(sub_dir, files);
+    }
+}
+
+// Every bundled file, with its path relative to the templates root
+pub fn builtin_files() -> Vec<(&'static Path, &'static [u8])> {
+    let mut files = Vec::new();
+    collect_files(&BUILTIN_TEMPLATES, &mut files);
+
+    let mut files: Vec<_> = files.into_iter()
+        .map(|file| (file.path(), file.contents()))
+        .collect();
+    files.sort_by_key(|(path, _)| *path);
+    files
+}
+
+// Identifies this exact set of bundled templates, hashed once per run
+fn builtin_digest() -> &'static str {
+    static DIGEST: OnceLock<String> = OnceLock::new();
+    DIGEST.get_or_init(|| {
+        let mut hasher = Sha256::new();
+        for (path, contents) in builtin_files() {
+            hasher.update(path.to_string_lossy().as_bytes());
+            hasher.update([0]);
+            hasher.update(contents);
+        }
+        hex::encode(hasher.finalize())
+    })
+}
+
+// The bundled templates are unpacked once per version into the cache, so
+// they can be read like any other templates directory
+pub fn builtin_templates_dir() -> Result<PathBuf> {
+    let builtin_root = get_cache_dir()
+        .unwrap_or_else(|_| std::env::temp_dir().join("vin-ui"))
+        .join("builtin");
+    let digest = builtin_digest();
+    let target = builtin_root.join(&digest[..16]);
+
+    if target.exists() {
+        return Ok(target);
+    }
+
+    // unpack next to the target and move into place, so an interrupted
+    // extraction is never mistaken for a complete one. Each process gets its
+    // own partial dir, so two running at once don't unpack over each other
+    let partial = builtin_root.join(format!("{}.{}.partial", &digest[..16], std::process::id()));
+    if partial.exists() {
+        fs::remove_dir_all(&partial)?;
+    }
+    fs::create_dir_all(&partial)?;
+
+    BUILTIN_TEMPLATES.extract(&partial)
+        .context(format!("Failed to unpack built-in templates to {}", partial.display()))?;
+    if let Err(err) = fs::rename(&partial, &target) {
+        // another process moved its copy into place first, the target only
+        // ever appears complete so that one is used
+        let _ = fs::remove_dir_all(&partial);
+        if !target.is_dir() {
+            return Err(err).context(format!("Failed to unpack built-in templates to {}", target.display()));
+        }
+    }
+
+    Ok(target)
+}
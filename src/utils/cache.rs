@@ -0,0 +1,197 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use anyhow::{Result, Context, bail};
+use sha2::{Digest, Sha256};
+
+use crate::models::cache::{CacheIndex, CacheEntry};
+
+pub enum VerifyStatus {
+    Ok,
+    Missing,
+    Corrupted(String), // Digest actually found on disk
+    Invalid,           // The index has something other than a digest for it
+}
+
+// Root of the cache, e.g. ~/.cache/vin-ui
+pub fn get_cache_dir() -> Result<PathBuf> {
+    let cache_dir = dirs::cache_dir()
+        .context("Could not find user cache directory")?;
+    Ok(cache_dir.join("vin-ui"))
+}
+
+// Payloads are stored by their SHA-256 so identical files are only kept once
+fn objects_dir() -> Result<PathBuf> {
+    Ok(get_cache_dir()?.join("objects"))
+}
+
+fn index_path() -> Result<PathBuf> {
+    Ok(get_cache_dir()?.join("index.json"))
+}
+
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+// First characters of a digest for display, the index is hand-editable so it may be shorter
+pub fn short_digest(digest: &str) -> &str {
+    digest.get(..12).unwrap_or(digest)
+}
+
+pub fn load_index() -> Result<CacheIndex> {
+    let path = index_path()?;
+    if !path.exists() {
+        return Ok(CacheIndex::default());
+    }
+
+    let content = fs::read_to_string(&path)
+        .context(format!("Failed to read cache index: {}", path.display()))?;
+
+    let index = serde_json::from_str(&content)
+        .context(format!("Failed to parse cache index: {}", path.display()))?;
+
+    Ok(index)
+}
+
+pub fn save_index(index: &CacheIndex) -> Result<()> {
+    let path = index_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let content = serde_json::to_string_pretty(index)?;
+    fs::write(&path, content)
+        .context(format!("Failed to write cache index: {}", path.display()))?;
+    Ok(())
+}
+
+// A SHA-256 as the cache writes it. The index is hand-editable, and its digests
+// become paths, so anything else (like `../../file`) never touches the disk
+pub fn is_digest(sha256: &str) -> bool {
+    sha256.len() == 64 && sha256.bytes().all(|c| c.is_ascii_digit() || (b'a'..=b'f').contains(&c))
+}
+
+pub fn object_path(sha256: &str) -> Result<PathBuf> {
+    if !is_digest(sha256) {
+        bail!("Invalid digest '{}' in the cache index", sha256);
+    }
+    Ok(objects_dir()?.join(sha256))
+}
+
+// Store a fetched payload and record which URL it came from
+pub fn store(url: &str, bytes: &[u8]) -> Result<String> {
+    let sha256 = sha256_hex(bytes);
+    let object = object_path(&sha256)?;
+
+    if !object.exists() {
+        if let Some(parent) = object.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&object, bytes)
+            .context(format!("Failed to write cache object: {}", object.display()))?;
+    }
+
+    let fetched_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    let mut index = load_index()?;
+    index.entries.insert(url.to_string(), CacheEntry {
+        sha256: sha256.clone(),
+        size: bytes.len() as u64,
+        fetched_at,
+    });
+    save_index(&index)?;
+
+    Ok(sha256)
+}
+
+// Cached payload for a URL, only if it still matches its recorded digest
+pub fn lookup(url: &str) -> Result<Option<Vec<u8>>> {
+    let index = load_index()?;
+    let entry = match index.entries.get(url) {
+        Some(entry) => entry,
+        None => return Ok(None),
+    };
+
+    // a broken entry is a miss, the payload is fetched again
+    if !is_digest(&entry.sha256) {
+        return Ok(None);
+    }
+
+    let object = object_path(&entry.sha256)?;
+    if !object.exists() {
+        return Ok(None);
+    }
+
+    let bytes = fs::read(&object)?;
+    if sha256_hex(&bytes) != entry.sha256 {
+        return Ok(None);
+    }
+
+    Ok(Some(bytes))
+}
+
+pub fn verify_entry(entry: &CacheEntry) -> Result<VerifyStatus> {
+    if !is_digest(&entry.sha256) {
+        return Ok(VerifyStatus::Invalid);
+    }
+
+    let object = object_path(&entry.sha256)?;
+    if !object.exists() {
+        return Ok(VerifyStatus::Missing);
+    }
+
+    let actual = sha256_hex(&fs::read(&object)?);
+    if actual != entry.sha256 {
+        return Ok(VerifyStatus::Corrupted(actual));
+    }
+
+    Ok(VerifyStatus::Ok)
+}
+
+// Remove the whole cache, returning the number of bytes freed
+pub fn clean() -> Result<u64> {
+    let cache_dir = get_cache_dir()?;
+    if !cache_dir.exists() {
+        return Ok(0);
+    }
+
+    let size = fs_extra::dir::get_size(&cache_dir).unwrap_or(0);
+    fs::remove_dir_all(&cache_dir)
+        .context(format!("Failed to remove cache directory: {}", cache_dir.display()))?;
+    Ok(size)
+}
+
+// Scratch directory for templates staged from a remote source, a package or
+// overlays, removed once dropped
+pub struct StagingDir {
+    path: PathBuf,
+}
+
+impl StagingDir {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for StagingDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+// Fresh staging dir for a key. Each process gets its own, so parallel runs
+// don't delete each other's files partway through an install
+pub fn prepare_staging_dir(key: &str) -> Result<StagingDir> {
+    let path = get_cache_dir()?
+        .join("staging")
+        .join(format!("{}.{}", &sha256_hex(key.as_bytes())[..16], std::process::id()));
+
+    if path.exists() {
+        fs::remove_dir_all(&path)?;
+    }
+    fs::create_dir_all(&path)?;
+    Ok(StagingDir { path })
+}
@@ -0,0 +1,710 @@
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::{Result, Context};
+
+use crate::models::project::ProjectInfo;
+
+// Finding Tailwind class strings in JS/TSX source: `className` attributes,
+// arguments of cn()/twMerge()/clsx()/cva() and variables named like
+// `baseClasses` or `gradientClasses` (including object maps of them).
+// Transforms work one class at a time; classes glued to a template literal
+// interpolation (e.g. `bg-${color}-500`) are left alone.
+
+const CLASS_FUNCTIONS: &[&str] = &["cn", "clsx", "classNames", "classnames", "twMerge", "twJoin", "cva", "tv"];
+
+const SCRIPT_EXTENSIONS: &[&str] = &["tsx", "ts", "jsx", "js", "mdx"];
+
+fn is_ident(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || c == b'_' || c == b'$'
+}
+
+fn is_class_variable(name: &str) -> bool {
+    name.to_lowercase().contains("class")
+}
+
+// Index just past the `}` matching the `{` at open, skipping nested strings
+fn brace_end(bytes: &[u8], open: usize) -> usize {
+    let mut depth = 0;
+    let mut i = open;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' | b'\'' | b'`' => {
+                i = string_end(bytes, i);
+                continue;
+            }
+            b'{' => depth += 1,
+            b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return i + 1;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    bytes.len()
+}
+
+// Index just past the closing quote of the string starting at start
+fn string_end(bytes: &[u8], start: usize) -> usize {
+    let quote = bytes[start];
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'$' if quote == b'`' && bytes.get(i + 1) == Some(&b'{') => i = brace_end(bytes, i + 1),
+            c if c == quote => return i + 1,
+            b'\n' if quote != b'`' => return i + 1,
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+// Map each whitespace separated class, except a first/last one glued to an interpolation
+fn map_tokens(text: &str, keep_first: bool, keep_last: bool, map: &dyn Fn(&str) -> String) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while !rest.is_empty() {
+        let token_end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        let (token, after) = rest.split_at(token_end);
+        if !token.is_empty() {
+            let glued = (keep_first && token.as_ptr() == text.as_ptr()) || (keep_last && after.is_empty());
+            output.push_str(&if glued { token.to_string() } else { map(token) });
+        }
+
+        let space_end = after.find(|c: char| !c.is_whitespace()).unwrap_or(after.len());
+        output.push_str(&after[..space_end]);
+        rest = &after[space_end..];
+    }
+    output
+}
+
+// A template literal's body: static parts are class lists, interpolations are scanned as class context
+fn map_template(body: &str, map: &dyn Fn(&str) -> String) -> String {
+    let bytes = body.as_bytes();
+    let mut output = String::with_capacity(body.len());
+    let mut part_start = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'$' if bytes.get(i + 1) == Some(&b'{') => {
+                let end = brace_end(bytes, i + 1);
+                let part = &body[part_start..i];
+                let keep_first = part_start > 0 && !part.starts_with(char::is_whitespace);
+                output.push_str(&map_tokens(part, keep_first, true, map));
+
+                output.push_str("${");
+                output.push_str(&scan(&body[i + 2..end - 1], true, map));
+                output.push('}');
+                i = end;
+                part_start = end;
+            }
+            _ => i += 1,
+        }
+    }
+
+    let part = &body[part_start.min(body.len())..];
+    let keep_first = part_start > 0 && !part.starts_with(char::is_whitespace);
+    output.push_str(&map_tokens(part, keep_first, false, map));
+    output
+}
+
+// Where the JSX text starting at i ends: the next tag or `{` expression
+fn jsx_text_end(bytes: &[u8], i: usize) -> usize {
+    bytes[i.min(bytes.len())..].iter()
+        .position(|c| *c == b'<' || *c == b'{')
+        .map(|at| i + at)
+        .unwrap_or(bytes.len())
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Pending {
+    None,
+    Attribute,              // className= : the next value only
+    Variable(usize),        // fooClasses = : until the statement ends at this depth
+}
+
+// Whether a `<` opens a JSX tag rather than comparing: `<div`, `</div`, `<>`, not after a value
+fn starts_tag(next: Option<u8>, previous: u8, last_word: &str) -> bool {
+    let opens = matches!(next, Some(b'/') | Some(b'>')) || next.is_some_and(|c| c.is_ascii_alphabetic());
+    let after_value = matches!(previous, b')' | b']' | b'"')
+        || (previous == b'a' && !matches!(last_word, "return" | "default" | "yield"));
+    opens && !after_value
+}
+
+fn scan(src: &str, class_context: bool, map: &dyn Fn(&str) -> String) -> String {
+    let bytes = src.as_bytes();
+    let mut output = String::with_capacity(src.len());
+    let mut copied = 0;
+    let mut stack: Vec<bool> = Vec::new();
+    let mut pending = Pending::None;
+    let mut last_word = String::new();
+    let mut previous = 0u8;         // previous significant character
+    let mut i = 0;
+
+    // JSX text (`<p>Don't</p>`) is skipped, its quotes don't start strings
+    let mut tag: Option<(usize, bool)> = None;          // In a tag: its stack depth, whether it closes
+    let mut elements = 0usize;                          // Elements open at this expression level
+    let mut containers: Vec<(usize, usize)> = Vec::new();   // `{}` in JSX text: stack depth, elements around it
+    let mut text_brace = false;                         // The next `{` is one of those
+
+    while i < bytes.len() {
+        let in_context = stack.last().copied().unwrap_or(class_context);
+        let c = bytes[i];
+
+        if c == b'/' && bytes.get(i + 1) == Some(&b'/') {
+            while i < bytes.len() && bytes[i] != b'\n' {
+                i += 1;
+            }
+            continue;
+        }
+        if c == b'/' && bytes.get(i + 1) == Some(&b'*') {
+            i = src[i + 2..].find("*/").map(|p| i + 2 + p + 2).unwrap_or(bytes.len());
+            continue;
+        }
+
+        match c {
+            b'"' | b'\'' | b'`' => {
+                let end = string_end(bytes, i);
+                let next = src[end..].trim_start().as_bytes().first().copied();
+                // strings compared against (`variant === "primary"`) are values, not classes
+                let compared = previous == b'=' || previous == b'!' || matches!(next, Some(b'=') | Some(b'!'));
+                let is_class = pending != Pending::None || (in_context && !compared);
+
+                if is_class && end - i >= 2 && bytes[end - 1] == c {
+                    output.push_str(&src[copied..=i]);
+                    let body = &src[i + 1..end - 1];
+                    if c == b'`' {
+                        output.push_str(&map_template(body, map));
+                    } else {
+                        output.push_str(&map_tokens(body, false, false, map));
+                    }
+                    copied = end - 1;
+                }
+
+                if pending == Pending::Attribute {
+                    pending = Pending::None;
+                }
+                previous = b'"';
+                last_word.clear();
+                i = end;
+                continue;
+            }
+            b'<' if tag.is_none() && starts_tag(bytes.get(i + 1).copied(), previous, &last_word) => {
+                tag = Some((stack.len(), bytes.get(i + 1) == Some(&b'/')));
+            }
+            b'>' if tag.is_some_and(|(depth, _)| depth == stack.len()) => {
+                let closing = tag.take().is_some_and(|(_, closing)| closing);
+                if closing {
+                    elements = elements.saturating_sub(1);
+                } else if previous != b'/' {
+                    elements += 1;
+                }
+
+                if elements > 0 {
+                    i = jsx_text_end(bytes, i + 1);
+                    text_brace = bytes.get(i) == Some(&b'{');
+                    previous = b'>';
+                    last_word.clear();
+                    continue;
+                }
+            }
+            b'{' | b'(' | b'[' => {
+                let class_call = c == b'(' && CLASS_FUNCTIONS.contains(&last_word.as_str());
+                stack.push(in_context || pending != Pending::None || class_call);
+                if pending == Pending::Attribute {
+                    pending = Pending::None;
+                }
+                if std::mem::take(&mut text_brace) {
+                    containers.push((stack.len() - 1, elements));
+                    elements = 0;
+                }
+            }
+            b'}' | b')' | b']' => {
+                stack.pop();
+                if let Pending::Variable(depth) = pending {
+                    if stack.len() < depth {
+                        pending = Pending::None;
+                    }
+                }
+
+                // back in the JSX text around the expression
+                if c == b'}' && containers.last().is_some_and(|(depth, _)| *depth == stack.len()) {
+                    elements = containers.pop().map(|(_, elements)| elements).unwrap_or_default();
+                    i = jsx_text_end(bytes, i + 1);
+                    text_brace = bytes.get(i) == Some(&b'{');
+                    previous = b'}';
+                    last_word.clear();
+                    continue;
+                }
+            }
+            b';' | b',' => {
+                if let Pending::Variable(depth) = pending {
+                    if stack.len() == depth {
+                        pending = Pending::None;
+                    }
+                }
+            }
+            b'\n' => {
+                // a line ending in a value ends a statement without semicolons
+                if let Pending::Variable(depth) = pending {
+                    if stack.len() == depth && (previous == b'"' || previous == b'a' || matches!(previous, b')' | b']' | b'}')) {
+                        pending = Pending::None;
+                    }
+                }
+            }
+            b'=' => {
+                let next = bytes.get(i + 1).copied();
+                let is_assignment = !matches!(next, Some(b'=') | Some(b'>'))
+                    && !matches!(previous, b'=' | b'!' | b'<' | b'>');
+                if is_assignment && is_class_variable(&last_word) {
+                    pending = if last_word == "className" || last_word == "class" {
+                        Pending::Attribute
+                    } else {
+                        Pending::Variable(stack.len())
+                    };
+                }
+            }
+            c if is_ident(c) => {
+                let start = i;
+                while i < bytes.len() && is_ident(bytes[i]) {
+                    i += 1;
+                }
+                last_word = src[start..i].to_string();
+                previous = b'a';
+                continue;
+            }
+            _ => {}
+        }
+
+        if !c.is_ascii_whitespace() {
+            previous = c;
+            if c != b'=' {
+                last_word.clear();
+            }
+        }
+        i += 1;
+    }
+
+    output.push_str(&src[copied..]);
+    output
+}
+
+// Apply map to every class in the class strings of a source file
+pub fn map_class_strings(src: &str, map: &dyn Fn(&str) -> String) -> String {
+    scan(src, false, map)
+}
+
+pub fn is_script_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| SCRIPT_EXTENSIONS.contains(&ext))
+}
+
+// Class names a stylesheet defines itself (`.om-timeline`, `.switch`, ...),
+// which are not Tailwind utilities and must not be transformed
+pub fn css_class_names(css: &str) -> BTreeSet<String> {
+    let bytes = css.as_bytes();
+    let mut names = BTreeSet::new();
+    let mut in_declarations: Vec<bool> = Vec::new();
+    let mut prelude_start = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = css[i + 2..].find("*/").map(|p| i + 2 + p + 2).unwrap_or(bytes.len());
+                prelude_start = i;
+                continue;
+            }
+            b'{' => {
+                let is_at_rule = css[prelude_start..i].trim_start().starts_with('@');
+                in_declarations.push(!is_at_rule);
+                prelude_start = i + 1;
+            }
+            b'}' => {
+                in_declarations.pop();
+                prelude_start = i + 1;
+            }
+            b';' => prelude_start = i + 1,
+            b'.' if !in_declarations.last().copied().unwrap_or(false) => {
+                let follows_digit = i > 0 && bytes[i - 1].is_ascii_digit();
+                let start = i + 1;
+                let mut end = start;
+                while end < bytes.len() && (bytes[end].is_ascii_alphanumeric() || bytes[end] == b'-' || bytes[end] == b'_') {
+                    end += 1;
+                }
+                let name = &css[start..end];
+                if !follows_digit && name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_' || c == '-') {
+                    names.insert(name.to_string());
+                }
+                i = end;
+                continue;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    names
+}
+
+// Where the variants of a class end: the last `:` outside arbitrary values like `[&:hover]`
+fn variants_end(class: &str) -> usize {
+    let mut depth = 0i32;
+    let mut end = 0;
+    for (i, c) in class.char_indices() {
+        match c {
+            '[' | '(' => depth += 1,
+            ']' | ')' => depth -= 1,
+            ':' if depth == 0 => end = i + 1,
+            _ => {}
+        }
+    }
+    end
+}
+
+// A class in parts: variants (`md:hover:`), `!`, `-` and the utility itself
+fn split_class(class: &str) -> (&str, &str, &str, &str) {
+    let (variants, utility) = class.split_at(variants_end(class));
+    let important = if utility.starts_with('!') { "!" } else { "" };
+    let utility = &utility[important.len()..];
+    let negative = if utility.starts_with('-') { "-" } else { "" };
+    (variants, important, negative, &utility[negative.len()..])
+}
+
+// Apply a Tailwind prefix to one class. A v4 prefix (`tw:`) goes in front of
+// everything, a v3 prefix (`tw-`) goes on the utility, after the variants and
+// the `!`/`-` modifiers: `hover:-translate-y-1` -> `hover:-tw-translate-y-1`
+pub fn prefix_class(class: &str, prefix: &str) -> String {
+    if prefix.ends_with(':') {
+        if class.starts_with(prefix) {
+            return class.to_string();
+        }
+        return format!("{}{}", prefix, class);
+    }
+
+    let (variants, important, negative, utility) = split_class(class);
+    if utility.is_empty() || utility.starts_with(prefix) {
+        return class.to_string();
+    }
+    format!("{}{}{}{}{}", variants, important, negative, prefix, utility)
+}
+
+// Physical utilities and their logical counterparts. Specific ones come before
+// the ones they start with (`rounded-tl` before `rounded-l`)
+const LOGICAL_UTILITIES: &[(&str, &str)] = &[
+    ("scroll-ml", "scroll-ms"), ("scroll-mr", "scroll-me"),
+    ("scroll-pl", "scroll-ps"), ("scroll-pr", "scroll-pe"),
+    ("rounded-tl", "rounded-ss"), ("rounded-tr", "rounded-se"),
+    ("rounded-bl", "rounded-es"), ("rounded-br", "rounded-ee"),
+    ("rounded-l", "rounded-s"), ("rounded-r", "rounded-e"),
+    ("border-l", "border-s"), ("border-r", "border-e"),
+    ("text-left", "text-start"), ("text-right", "text-end"),
+    ("float-left", "float-start"), ("float-right", "float-end"),
+    ("clear-left", "clear-start"), ("clear-right", "clear-end"),
+    ("ml", "ms"), ("mr", "me"), ("pl", "ps"), ("pr", "pe"),
+    ("left", "start"), ("right", "end"),
+];
+
+// Utilities tied to a physical side that have no logical counterpart
+const PHYSICAL_ONLY: &[&str] = &[
+    "space-x", "divide-x", "translate-x", "skew-x",
+    "bg-gradient-to-r", "bg-gradient-to-l", "bg-gradient-to-tr", "bg-gradient-to-tl",
+    "bg-gradient-to-br", "bg-gradient-to-bl", "bg-linear-to-r", "bg-linear-to-l",
+    "bg-linear-to-tr", "bg-linear-to-tl", "bg-linear-to-br", "bg-linear-to-bl",
+    "origin-left", "origin-right", "origin-top-left", "origin-top-right",
+    "origin-bottom-left", "origin-bottom-right", "bg-left", "bg-right",
+    "object-left", "object-right",
+];
+
+// `utility` is `name` itself or `name-<value>`
+fn has_utility_name(utility: &str, name: &str) -> bool {
+    utility.strip_prefix(name).is_some_and(|rest| rest.is_empty() || rest.starts_with('-'))
+}
+
+// Rewrite a physical utility to its logical counterpart, so it follows the
+// text direction: `ml-4` -> `ms-4`, `md:-left-2` -> `md:-start-2`.
+// None when the class depends on the physical side and can't be converted.
+pub fn logical_class(class: &str) -> Option<String> {
+    let (variants, important, negative, utility) = split_class(class);
+
+    // `rtl:`/`ltr:` variants already handle the direction themselves
+    if variants.contains("rtl:") || variants.contains("ltr:") {
+        return Some(class.to_string());
+    }
+
+    if PHYSICAL_ONLY.iter().any(|name| has_utility_name(utility, name)) {
+        return None;
+    }
+
+    match LOGICAL_UTILITIES.iter().find(|(physical, _)| has_utility_name(utility, physical)) {
+        Some((physical, logical)) => Some(format!(
+            "{}{}{}{}{}",
+            variants, important, negative, logical, &utility[physical.len()..]
+        )),
+        None => Some(class.to_string()),
+    }
+}
+
+// Swap the color of a color utility for an arbitrary value: `from-[#4C43CD]` or `via-purple-500/50`
+// with `#4C43CD`/`purple-500` mapped to `color:var(--vin-primary)` -> `from-[color:var(--vin-primary)]`
+pub fn recolor_class(class: &str, colors: &BTreeMap<String, String>) -> String {
+    let (variants, utility) = class.split_at(variants_end(class));
+    let (utility, opacity) = match utility.rfind('/') {
+        Some(at) if !utility[at..].contains(']') => utility.split_at(at),
+        _ => (utility, ""),
+    };
+
+    for (template_color, replacement) in colors {
+        let suffix = if template_color.starts_with('#') {
+            format!("-[{}]", template_color)
+        } else {
+            format!("-{}", template_color)
+        };
+
+        if utility.len() > suffix.len() && utility.to_lowercase().ends_with(&suffix.to_lowercase()) {
+            let base = &utility[..utility.len() - suffix.len()];
+            return format!("{}{}-[{}]{}", variants, base, replacement, opacity);
+        }
+    }
+    class.to_string()
+}
+
+// What to do to the classes of the files an add installs
+pub struct ClassTransforms {
+    pub prefix: Option<String>,             // Tailwind prefix of the project
+    pub custom_classes: BTreeSet<String>,   // Classes the item's own CSS defines, left alone
+    pub colors: BTreeMap<String, String>,   // Template color -> arbitrary value replacing it, like `color:var(--vin-primary)`
+    pub rtl: bool,                          // Rewrite physical utilities to logical ones
+    unconverted: RefCell<BTreeMap<PathBuf, BTreeSet<String>>>,  // Classes rtl couldn't rewrite, by file
+}
+
+impl ClassTransforms {
+    pub fn new(project_info: &ProjectInfo) -> Self {
+        ClassTransforms {
+            prefix: project_info.tailwind.as_ref().and_then(|tailwind| tailwind.prefix.clone()),
+            custom_classes: BTreeSet::new(),
+            colors: BTreeMap::new(),
+            rtl: false,
+            unconverted: RefCell::new(BTreeMap::new()),
+        }
+    }
+
+    // Leave the classes a stylesheet being installed defines alone
+    pub fn keep_classes_of(&mut self, css: &str) {
+        self.custom_classes.extend(css_class_names(css));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.prefix.is_none() && self.colors.is_empty() && !self.rtl
+    }
+
+    // Only exact names, a stylesheet defining `.text` says nothing about `text-left`
+    fn is_custom(&self, class: &str) -> bool {
+        self.custom_classes.contains(class)
+    }
+
+    fn apply(&self, class: &str, path: &Path) -> String {
+        if self.is_custom(class) {
+            return class.to_string();
+        }
+
+        let mut class = recolor_class(class, &self.colors);
+        if self.rtl {
+            match logical_class(&class) {
+                Some(logical) => class = logical,
+                None => {
+                    self.unconverted.borrow_mut().entry(path.to_path_buf()).or_default().insert(class.clone());
+                }
+            }
+        }
+
+        match &self.prefix {
+            Some(prefix) => prefix_class(&class, prefix),
+            None => class,
+        }
+    }
+
+    // Rewrite the classes of an installed file in place
+    pub fn apply_to_file(&self, path: &Path) -> Result<()> {
+        if self.is_empty() || !is_script_file(path) {
+            return Ok(());
+        }
+
+        let Ok(content) = fs::read_to_string(path) else {
+            return Ok(());
+        };
+
+        let rewritten = map_class_strings(&content, &|class| self.apply(class, path));
+        if rewritten != content {
+            fs::write(path, rewritten)
+                .context(format!("Failed to update classes in {}", path.display()))?;
+        }
+        Ok(())
+    }
+
+    // Classes of the installed files rtl left physical, to check by hand
+    pub fn unconverted(&self) -> BTreeMap<PathBuf, BTreeSet<String>> {
+        self.unconverted.borrow().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transforms(prefix: Option<&str>, custom: &[&str]) -> ClassTransforms {
+        ClassTransforms {
+            prefix: prefix.map(str::to_string),
+            custom_classes: custom.iter().map(|class| class.to_string()).collect(),
+            colors: BTreeMap::new(),
+            rtl: false,
+            unconverted: RefCell::new(BTreeMap::new()),
+        }
+    }
+
+    fn rewrite(transforms: &ClassTransforms, src: &str) -> String {
+        map_class_strings(src, &|class| transforms.apply(class, Path::new("test.tsx")))
+    }
+
+    #[test]
+    fn v3_prefix_goes_on_the_utility() {
+        assert_eq!(prefix_class("flex", "tw-"), "tw-flex");
+        assert_eq!(prefix_class("md:hover:bg-white", "tw-"), "md:hover:tw-bg-white");
+        assert_eq!(prefix_class("hover:-translate-y-1", "tw-"), "hover:-tw-translate-y-1");
+        assert_eq!(prefix_class("!mt-0", "tw-"), "!tw-mt-0");
+        assert_eq!(prefix_class("md:!-mt-2", "tw-"), "md:!-tw-mt-2");
+    }
+
+    #[test]
+    fn v3_prefix_keeps_arbitrary_values_whole() {
+        assert_eq!(prefix_class("w-[10px]", "tw-"), "tw-w-[10px]");
+        assert_eq!(prefix_class("[&:hover]:text-white", "tw-"), "[&:hover]:tw-text-white");
+        assert_eq!(prefix_class("bg-[url(a:b.png)]", "tw-"), "tw-bg-[url(a:b.png)]");
+        assert_eq!(prefix_class("tw-flex", "tw-"), "tw-flex");
+    }
+
+    #[test]
+    fn v4_prefix_goes_in_front() {
+        assert_eq!(prefix_class("flex", "tw:"), "tw:flex");
+        assert_eq!(prefix_class("hover:-translate-y-1", "tw:"), "tw:hover:-translate-y-1");
+        assert_eq!(prefix_class("md:w-[10px]", "tw:"), "tw:md:w-[10px]");
+        assert_eq!(prefix_class("tw:hover:flex", "tw:"), "tw:hover:flex");
+    }
+
+    #[test]
+    fn prefixes_class_strings() {
+        let v3 = transforms(Some("tw-"), &[]);
+        assert_eq!(
+            rewrite(&v3, r#"<div className="flex hover:-mt-1 w-[10px]" />"#),
+            r#"<div className="tw-flex hover:-tw-mt-1 tw-w-[10px]" />"#
+        );
+        assert_eq!(
+            rewrite(&v3, r#"cn("p-2", active && "md:!p-4")"#),
+            r#"cn("tw-p-2", active && "md:!tw-p-4")"#
+        );
+
+        let v4 = transforms(Some("tw:"), &[]);
+        assert_eq!(
+            rewrite(&v4, r#"<div className="flex hover:-mt-1" />"#),
+            r#"<div className="tw:flex tw:hover:-mt-1" />"#
+        );
+    }
+
+    #[test]
+    fn custom_classes_match_exactly() {
+        let transforms = transforms(Some("tw-"), &["text", "om-timeline"]);
+        assert_eq!(
+            rewrite(&transforms, r#"<p className="text text-left om-timeline om-timeline-circle" />"#),
+            r#"<p className="text tw-text-left om-timeline tw-om-timeline-circle" />"#
+        );
+    }
+
+    #[test]
+    fn quotes_in_jsx_text_are_not_strings() {
+        let transforms = transforms(Some("tw-"), &[]);
+        let src = r#"<p className="p-2">Don't {count > 1 ? "items" : "item"} stop</p>
+<span className="flex">It's {name}'s</span>
+<p>Don't <b className="font-bold">stop</b></p>
+const x = a < b ? cn("m-1") : null;"#;
+        assert_eq!(
+            rewrite(&transforms, src),
+            r#"<p className="tw-p-2">Don't {count > 1 ? "items" : "item"} stop</p>
+<span className="tw-flex">It's {name}'s</span>
+<p>Don't <b className="tw-font-bold">stop</b></p>
+const x = a < b ? cn("tw-m-1") : null;"#
+        );
+    }
+
+    #[test]
+    fn rtl_rewrites_physical_utilities() {
+        assert_eq!(logical_class("ml-4").as_deref(), Some("ms-4"));
+        assert_eq!(logical_class("mr-auto").as_deref(), Some("me-auto"));
+        assert_eq!(logical_class("pl-2").as_deref(), Some("ps-2"));
+        assert_eq!(logical_class("md:-left-2").as_deref(), Some("md:-start-2"));
+        assert_eq!(logical_class("right-0").as_deref(), Some("end-0"));
+        assert_eq!(logical_class("text-left").as_deref(), Some("text-start"));
+        assert_eq!(logical_class("hover:!text-right").as_deref(), Some("hover:!text-end"));
+        assert_eq!(logical_class("rounded-l-lg").as_deref(), Some("rounded-s-lg"));
+        assert_eq!(logical_class("rounded-tr").as_deref(), Some("rounded-se"));
+        assert_eq!(logical_class("border-r-2").as_deref(), Some("border-e-2"));
+        assert_eq!(logical_class("float-left").as_deref(), Some("float-start"));
+    }
+
+    #[test]
+    fn rtl_leaves_other_utilities_alone() {
+        assert_eq!(logical_class("mx-4").as_deref(), Some("mx-4"));
+        assert_eq!(logical_class("m-4").as_deref(), Some("m-4"));
+        assert_eq!(logical_class("text-lg").as_deref(), Some("text-lg"));
+        assert_eq!(logical_class("rounded-lg").as_deref(), Some("rounded-lg"));
+        assert_eq!(logical_class("leading-6").as_deref(), Some("leading-6"));
+        assert_eq!(logical_class("rtl:ml-2").as_deref(), Some("rtl:ml-2"));
+        assert_eq!(logical_class("space-x-4"), None);
+        assert_eq!(logical_class("bg-gradient-to-r"), None);
+        assert_eq!(logical_class("-translate-x-1/2"), None);
+    }
+
+    #[test]
+    fn rtl_skips_custom_classes() {
+        let mut transforms = transforms(None, &["right-side"]);
+        transforms.rtl = true;
+        assert_eq!(
+            rewrite(&transforms, r#"<div className="right-side ml-2 space-x-2" />"#),
+            r#"<div className="right-side ms-2 space-x-2" />"#
+        );
+
+        let unconverted = transforms.unconverted();
+        let classes: Vec<&String> = unconverted.values().flatten().collect();
+        assert_eq!(classes, ["space-x-2"]);
+    }
+
+    #[test]
+    fn rtl_then_prefix() {
+        let mut transforms = transforms(Some("tw-"), &[]);
+        transforms.rtl = true;
+        assert_eq!(
+            rewrite(&transforms, r#"cn("md:-left-2 text-right")"#),
+            r#"cn("md:-tw-start-2 tw-text-end")"#
+        );
+    }
+
+    #[test]
+    fn finds_class_names_in_css() {
+        let names = css_class_names(".om-timeline { background: url(x.png) } .a:hover, .b::after { } /* .c */ @media (min-width: 1.5rem) { .d { } }");
+        assert!(names.contains("om-timeline"));
+        assert!(names.contains("a"));
+        assert!(names.contains("b"));
+        assert!(names.contains("d"));
+        assert!(!names.contains("png"));
+        assert!(!names.contains("c"));
+        assert!(!names.contains("5rem"));
+    }
+}
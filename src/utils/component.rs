@@ -1,9 +1,12 @@
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::Path;
-use anyhow::{Result, Context};
+use std::path::{Path, PathBuf};
+use anyhow::{Result, Context, bail};
 use serde_json;
 
-use crate::models::component::ComponentConfig;
+use crate::models::component::{AdditionalFile, ComponentConfig, FileType};
+use crate::utils::block::is_relative_path;
+use crate::utils::hook::hook_template_files;
 
 pub fn load_component_config(config_path: &Path) -> Result<Option<ComponentConfig>> {
     if !config_path.exists() {
@@ -20,6 +23,203 @@ pub fn load_component_config(config_path: &Path) -> Result<Option<ComponentConfi
 }
 
 
+// A component is either `<name>.tsx` (with an optional `<name>.json`) at the top
+// of the templates dir, or a `<name>/` directory holding `<name>.tsx`, its
+// `<name>.json` config and any related files, installed together as a unit
+pub fn is_directory_component(template_dir: &Path, component_name: &str) -> bool {
+    template_dir.join(component_name).join(format!("{}.tsx", component_name)).is_file()
+}
+
+// Entry file of a component, relative to the templates dir
+pub fn component_entry_file(template_dir: &Path, component_name: &str) -> String {
+    if is_directory_component(template_dir, component_name) {
+        format!("{}/{}.tsx", component_name, component_name)
+    } else {
+        format!("{}.tsx", component_name)
+    }
+}
+
+// Config file of a component, relative to the templates dir
+pub fn component_config_file(template_dir: &Path, component_name: &str) -> String {
+    if is_directory_component(template_dir, component_name) {
+        format!("{}/{}.json", component_name, component_name)
+    } else {
+        format!("{}.json", component_name)
+    }
+}
+
+// All files below root, keyed by their '/'-separated relative path
+pub fn list_template_files(root: &Path) -> Result<BTreeMap<String, PathBuf>> {
+    fn walk(root: &Path, dir: &Path, files: &mut BTreeMap<String, PathBuf>) -> Result<()> {
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                walk(root, &path, files)?;
+            } else if let Ok(relative) = path.strip_prefix(root) {
+                let relative: Vec<String> = relative.components()
+                    .map(|c| c.as_os_str().to_string_lossy().to_string())
+                    .collect();
+                files.insert(relative.join("/"), path);
+            }
+        }
+        Ok(())
+    }
+
+    let mut files = BTreeMap::new();
+    if root.is_dir() {
+        walk(root, root, &mut files)?;
+    }
+    Ok(files)
+}
+
+// Every template file (relative to the templates dir) installing a component reads
+pub fn component_template_files(template_dir: &Path, component_name: &str, config: Option<&ComponentConfig>) -> Vec<String> {
+    let mut files = if is_directory_component(template_dir, component_name) {
+        list_template_files(&template_dir.join(component_name))
+            .unwrap_or_default()
+            .into_keys()
+            .map(|file| format!("{}/{}", component_name, file))
+            .collect()
+    } else {
+        let mut files = vec![
+            format!("{}.tsx", component_name),
+            format!("{}.json", component_name),
+        ];
+        let variants = config.and_then(|config| config.tailwind.as_ref()).and_then(|tailwind| tailwind.variants.as_ref());
+        files.extend(variants.into_iter().flat_map(|variants| variants.values().cloned()));
+        files.extend(config.into_iter().flat_map(style_files));
+        files
+    };
+
+    if let Some(config) = config {
+        for util in config.utils.iter().flatten() {
+            files.push(format!("utils/{}.ts", util));
+        }
+        for hook in config.hooks.iter().flatten() {
+            files.extend(hook_template_files(template_dir, hook));
+        }
+        for file_info in config.additional_files.iter().flatten() {
+            if is_glob(&file_info.source) {
+                let matches = glob_matches(&template_dir.join("utils"), &file_info.source).unwrap_or_default();
+                files.extend(matches.into_iter().map(|file| format!("utils/{}", file)));
+            } else {
+                files.push(format!("utils/{}", file_info.source));
+            }
+        }
+    }
+
+    files
+}
+
+
+// Every file of the component's alternate styles, entries and stylesheets
+pub fn style_files(config: &ComponentConfig) -> Vec<String> {
+    config.styles.iter()
+        .flatten()
+        .flat_map(|(_, style)| std::iter::once(style.entry.clone()).chain(style.files.clone().unwrap_or_default()))
+        .collect()
+}
+
+// One concrete file installed for an `additional_files` entry
+pub struct ExpandedFile {
+    pub source: String,         // Relative to templates/utils
+    pub dest: String,           // Relative to the target dir of its type
+    pub file_type: FileType,
+}
+
+// Only `*` and `?`, since `[slug]` and `[id]` are Next.js dynamic route segments
+pub fn is_glob(source: &str) -> bool {
+    source.contains(['*', '?'])
+}
+
+// Leading components of a glob without wildcards, e.g. "icons" for "icons/*.svg"
+fn glob_base(source: &str) -> String {
+    source.split('/')
+        .take_while(|part| !is_glob(part))
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+// Files under base matched by a glob, as '/'-separated paths relative to base
+fn glob_matches(base: &Path, source: &str) -> Result<Vec<String>> {
+    // brackets are literal, `app/[slug]/*.tsx` is the `[slug]` directory
+    let source: String = source.chars()
+        .map(|c| match c {
+            '[' => "[[]".to_string(),
+            ']' => "[]]".to_string(),
+            c => c.to_string(),
+        })
+        .collect();
+    let pattern = format!("{}/{}", glob::Pattern::escape(&base.to_string_lossy()), source);
+    let mut matches = Vec::new();
+
+    for path in glob::glob(&pattern).context(format!("Invalid glob pattern '{}'", source))? {
+        let path = path?;
+        if !path.is_file() {
+            continue;
+        }
+        if let Ok(relative) = path.strip_prefix(base) {
+            let relative: Vec<String> = relative.components()
+                .map(|c| c.as_os_str().to_string_lossy().to_string())
+                .collect();
+            matches.push(relative.join("/"));
+        }
+    }
+
+    matches.sort();
+    Ok(matches)
+}
+
+// Expand an additional file entry into the files it installs. For glob sources,
+// `dest` can use {path} (the match relative to the glob's base dir) and {name}
+// (its file name); a dest without placeholders is a directory the relative
+// paths are kept under, and no dest keeps the source path.
+pub fn expand_additional_file(utils_dir: &Path, file_info: &AdditionalFile) -> Result<Vec<ExpandedFile>> {
+    // configs come from remote URLs and packages too, so neither path may leave its directory
+    if !is_relative_path(&file_info.source) {
+        bail!("Additional file '{}' has an invalid source, expected a path inside templates/utils", file_info.source);
+    }
+    let expanded = expand_paths(utils_dir, file_info)?;
+    if let Some(file) = expanded.iter().find(|file| !is_relative_path(&file.dest)) {
+        bail!("Additional file '{}' has an invalid destination '{}'", file.source, file.dest);
+    }
+    Ok(expanded)
+}
+
+fn expand_paths(utils_dir: &Path, file_info: &AdditionalFile) -> Result<Vec<ExpandedFile>> {
+    let file_type = |path: &str| file_info.file_type.unwrap_or_else(|| FileType::from_extension(path));
+
+    if !is_glob(&file_info.source) {
+        return Ok(vec![ExpandedFile {
+            source: file_info.source.clone(),
+            dest: file_info.dest.clone().unwrap_or(file_info.source.clone()),
+            file_type: file_type(&file_info.source),
+        }]);
+    }
+
+    let matches = glob_matches(utils_dir, &file_info.source)?;
+    if matches.is_empty() {
+        bail!("Glob '{}' in additional_files matched no files in {}", file_info.source, utils_dir.display());
+    }
+
+    let base = glob_base(&file_info.source);
+    Ok(matches.into_iter()
+        .map(|matched| {
+            let relative = matched.strip_prefix(&base).unwrap_or(&matched).trim_start_matches('/');
+            let name = matched.rsplit('/').next().unwrap_or(&matched);
+
+            let dest = match &file_info.dest {
+                Some(dest) if dest.contains('{') => dest.replace("{path}", relative).replace("{name}", name),
+                Some(dest) => format!("{}/{}", dest.trim_end_matches('/'), relative),
+                None => matched.clone(),
+            };
+
+            ExpandedFile { file_type: file_type(&matched), source: matched, dest }
+        })
+        .collect())
+}
+
+
 // Lists all available components in the templates directory
 pub fn get_available_components(template_dir: &Path) -> Result<Vec<String>> {
     if !template_dir.exists() {
@@ -29,20 +229,27 @@ pub fn get_available_components(template_dir: &Path) -> Result<Vec<String>> {
     let entries = fs::read_dir(template_dir)?;
     let mut components = Vec::new();
 
-    for entry in entries {
-        if let Ok(entry) = entry {
-            let path = entry.path();
-            if path.is_file() && path.extension().map_or(false, |ext| ext == "tsx") {
-                if let Some(stem) = path.file_stem(){
-                    if let Some(name) = stem.to_str() {
-                        components.push(name.to_string());
-                    }
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_file() && path.extension().is_some_and(|ext| ext == "tsx") {
+            if let Some(stem) = path.file_stem(){
+                // files like button.tw3.tsx are variants of a component, not components
+                if let Some(name) = stem.to_str().filter(|name| !name.contains('.')) {
+                    components.push(name.to_string());
+                }
+            }
+        } else if path.is_dir() {
+            // directory components
+            if let Some(name) = path.file_name().and_then(|name| name.to_str()) {
+                if is_directory_component(template_dir, name) {
+                    components.push(name.to_string());
                 }
             }
         }
     }
     
     components.sort();
+    components.dedup();
     Ok(components)
 }
 
@@ -74,3 +281,126 @@ pub fn add_utility(lib_dir: &Path, util_name: &str, template_dir: &Path) -> Resu
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    // A templates/utils dir in the temp dir, removed when dropped
+    struct TempUtils(PathBuf);
+
+    impl TempUtils {
+        fn new(name: &str, files: &[&str]) -> Self {
+            let path = std::env::temp_dir().join(format!("vin-ui-{}-{}", std::process::id(), name));
+            let _ = fs::remove_dir_all(&path);
+            for file in files {
+                let file = path.join(file);
+                fs::create_dir_all(file.parent().unwrap()).unwrap();
+                fs::write(file, "").unwrap();
+            }
+            fs::create_dir_all(&path).unwrap();
+            TempUtils(path)
+        }
+
+        fn expand(&self, source: &str, dest: Option<&str>) -> Result<Vec<(String, String)>> {
+            let file_info = AdditionalFile {
+                source: source.to_string(),
+                dest: dest.map(str::to_string),
+                description: None,
+                file_type: None,
+            };
+            Ok(expand_additional_file(&self.0, &file_info)?
+                .into_iter()
+                .map(|file| (file.source, file.dest))
+                .collect())
+        }
+    }
+
+    impl Drop for TempUtils {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+        expected.iter().map(|(source, dest)| (source.to_string(), dest.to_string())).collect()
+    }
+
+    #[test]
+    fn only_stars_and_question_marks_are_wildcards() {
+        assert!(is_glob("icons/*.svg"));
+        assert!(is_glob("icons/**/icon-?.svg"));
+        assert!(!is_glob("app/[slug]/page.tsx"));
+        assert!(!is_glob("timeline-data.ts"));
+    }
+
+    #[test]
+    fn plain_sources_are_kept_as_they_are() {
+        let utils = TempUtils::new("expand-plain", &[]);
+        assert_eq!(utils.expand("timeline-data.ts", None).unwrap(), pairs(&[("timeline-data.ts", "timeline-data.ts")]));
+        assert_eq!(utils.expand("timeline-data.ts", Some("data/timeline.ts")).unwrap(), pairs(&[("timeline-data.ts", "data/timeline.ts")]));
+    }
+
+    #[test]
+    fn double_star_keeps_subpaths() {
+        let utils = TempUtils::new("expand-subpaths", &["icons/a.svg", "icons/social/b.svg", "icons/social/x/c.svg", "icons/readme.md"]);
+
+        assert_eq!(
+            utils.expand("icons/**/*.svg", Some("icons")).unwrap(),
+            pairs(&[("icons/a.svg", "icons/a.svg"), ("icons/social/b.svg", "icons/social/b.svg"), ("icons/social/x/c.svg", "icons/social/x/c.svg")])
+        );
+        assert_eq!(
+            utils.expand("icons/**/*.svg", None).unwrap(),
+            pairs(&[("icons/a.svg", "icons/a.svg"), ("icons/social/b.svg", "icons/social/b.svg"), ("icons/social/x/c.svg", "icons/social/x/c.svg")])
+        );
+        assert_eq!(
+            utils.expand("icons/**/*.svg", Some("assets/{path}")).unwrap(),
+            pairs(&[("icons/a.svg", "assets/a.svg"), ("icons/social/b.svg", "assets/social/b.svg"), ("icons/social/x/c.svg", "assets/social/x/c.svg")])
+        );
+        assert_eq!(
+            utils.expand("icons/**/*.svg", Some("flat/{name}")).unwrap(),
+            pairs(&[("icons/a.svg", "flat/a.svg"), ("icons/social/b.svg", "flat/b.svg"), ("icons/social/x/c.svg", "flat/c.svg")])
+        );
+        // a single star stays in its directory
+        assert_eq!(utils.expand("icons/*.svg", Some("icons")).unwrap(), pairs(&[("icons/a.svg", "icons/a.svg")]));
+    }
+
+    #[test]
+    fn brackets_are_literal() {
+        let utils = TempUtils::new("expand-brackets", &["app/[slug]/page.tsx", "app/[slug]/layout.tsx", "app/s/page.tsx", "app/g/page.tsx"]);
+
+        assert_eq!(
+            utils.expand("app/[slug]/*.tsx", Some("blog/{path}")).unwrap(),
+            pairs(&[("app/[slug]/layout.tsx", "blog/layout.tsx"), ("app/[slug]/page.tsx", "blog/page.tsx")])
+        );
+        assert_eq!(
+            utils.expand("app/**/page.tsx", None).unwrap(),
+            pairs(&[("app/[slug]/page.tsx", "app/[slug]/page.tsx"), ("app/g/page.tsx", "app/g/page.tsx"), ("app/s/page.tsx", "app/s/page.tsx")])
+        );
+    }
+
+    #[test]
+    fn a_glob_matching_nothing_is_an_error() {
+        let utils = TempUtils::new("expand-nothing", &["icons/a.svg"]);
+        let err = utils.expand("icons/*.png", None).unwrap_err();
+        assert!(err.to_string().contains("matched no files"), "{}", err);
+    }
+
+    #[test]
+    fn paths_leaving_their_directory_are_rejected() {
+        let utils = TempUtils::new("expand-escape", &["icons/a.svg"]);
+
+        for dest in ["../outside.ts", "/etc/passwd", "icons/../../x.ts"] {
+            let err = utils.expand("icons/a.svg", Some(dest)).unwrap_err();
+            assert!(err.to_string().contains("invalid destination"), "{}: {}", dest, err);
+        }
+        let err = utils.expand("icons/*.svg", Some("../{name}")).unwrap_err();
+        assert!(err.to_string().contains("invalid destination"), "{}", err);
+
+        for source in ["../secrets.ts", "/etc/passwd", "icons/../../x.ts"] {
+            let err = utils.expand(source, None).unwrap_err();
+            assert!(err.to_string().contains("invalid source"), "{}: {}", source, err);
+        }
+    }
+}
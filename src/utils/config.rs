@@ -0,0 +1,68 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::{Result, Context, bail};
+use serde_json::{Map, Value};
+
+use crate::models::config::{ProjectConfig, UserConfig};
+
+pub const PROJECT_CONFIG_FILE: &str = "vin-ui.json";
+
+pub fn get_config_path() -> Result<PathBuf> {
+    let config_dir = dirs::config_dir()
+        .context("Could not find user config directory")?;
+    Ok(config_dir.join("vin-ui").join("config.json"))
+}
+
+pub fn load_user_config() -> Result<UserConfig> {
+    let config_path = get_config_path()?;
+    if !config_path.exists() {
+        return Ok(UserConfig::default());
+    }
+
+    let content = fs::read_to_string(&config_path)
+        .context(format!("Failed to read config file: {}", config_path.display()))?;
+
+    let config = serde_json::from_str(&content)
+        .context(format!("Failed to parse config file: {}", config_path.display()))?;
+
+    Ok(config)
+}
+
+pub fn load_project_config(project_root: &Path) -> Result<ProjectConfig> {
+    let config_path = project_root.join(PROJECT_CONFIG_FILE);
+    if !config_path.exists() {
+        return Ok(ProjectConfig::default());
+    }
+
+    let content = fs::read_to_string(&config_path)
+        .context(format!("Failed to read config file: {}", config_path.display()))?;
+
+    let config = serde_json::from_str(&content)
+        .context(format!("Failed to parse config file: {}", config_path.display()))?;
+
+    Ok(config)
+}
+
+// Set one key of vin-ui.json, leaving the rest of the file as it is, including
+// keys vin-ui doesn't know about like "$schema"
+pub fn set_project_config_value(project_root: &Path, key: &str, value: Value) -> Result<()> {
+    let config_path = project_root.join(PROJECT_CONFIG_FILE);
+    let mut config = if config_path.exists() {
+        let content = fs::read_to_string(&config_path)
+            .context(format!("Failed to read config file: {}", config_path.display()))?;
+        serde_json::from_str(&content)
+            .context(format!("Failed to parse config file: {}", config_path.display()))?
+    } else {
+        Value::Object(Map::new())
+    };
+
+    let Some(object) = config.as_object_mut() else {
+        bail!("{} is not a JSON object", config_path.display());
+    };
+    object.insert(key.to_string(), value);
+
+    let content = serde_json::to_string_pretty(&config)?;
+    fs::write(&config_path, content + "\n")
+        .context(format!("Failed to write config file: {}", config_path.display()))?;
+    Ok(())
+}
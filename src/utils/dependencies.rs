@@ -0,0 +1,259 @@
+use std::path::Path;
+use semver::{Version, VersionReq};
+
+use crate::models::component::{ComponentConfig, Dependencies};
+use crate::utils::project::{get_installed_version, get_package_version, package_name, split_dependency};
+
+// Packages an item needs, from its config and the configs of the hooks it uses
+#[derive(Debug, Default)]
+pub struct DependencySet {
+    pub dependencies: Vec<String>,          // Specs for the package manager, `name` or `name@range`
+    pub dev_dependencies: Vec<String>,
+    pub peer_dependencies: Vec<String>,
+}
+
+fn specs(dependencies: Option<&Dependencies>) -> Vec<String> {
+    dependencies.map(Dependencies::specs).unwrap_or_default()
+}
+
+// Add the specs for packages that aren't listed yet, the first range asked for wins
+fn merge_specs(into: &mut Vec<String>, specs: Vec<String>) {
+    for spec in specs {
+        if !into.iter().any(|existing| package_name(existing) == package_name(&spec)) {
+            into.push(spec);
+        }
+    }
+}
+
+impl DependencySet {
+    pub fn new(dependencies: Option<&Dependencies>, dev_dependencies: Option<&Dependencies>, peer_dependencies: Option<&Dependencies>) -> Self {
+        DependencySet {
+            dependencies: specs(dependencies),
+            dev_dependencies: specs(dev_dependencies),
+            peer_dependencies: specs(peer_dependencies),
+        }
+    }
+
+    pub fn of(config: &ComponentConfig) -> Self {
+        DependencySet::new(config.dependencies.as_ref(), config.dev_dependencies.as_ref(), config.peer_dependencies.as_ref())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.dependencies.is_empty() && self.dev_dependencies.is_empty() && self.peer_dependencies.is_empty()
+    }
+
+    pub fn merge(&mut self, other: DependencySet) {
+        merge_specs(&mut self.dependencies, other.dependencies);
+        merge_specs(&mut self.dev_dependencies, other.dev_dependencies);
+        merge_specs(&mut self.peer_dependencies, other.peer_dependencies);
+    }
+}
+
+pub enum PeerCheck {
+    Satisfied,
+    Missing,                    // Not in package.json
+    Mismatch(String),           // The project's version, outside the range
+    Unknown(String),            // The project's version or range, when either can't be compared
+}
+
+// "18" -> 18.0.0, "18.2.x" -> 18.2.0
+fn padded_version(version: &str) -> Option<Version> {
+    let version = version.trim().trim_start_matches('v');
+    let (core, rest) = match version.find(['-', '+']) {
+        Some(at) => version.split_at(at),
+        None => (version, ""),
+    };
+
+    let mut parts: Vec<&str> = core.split('.').map(|part| if part == "x" || part == "X" || part == "*" { "0" } else { part }).collect();
+    if parts.is_empty() || parts.len() > 3 {
+        return None;
+    }
+    parts.resize(3, "0");
+    Version::parse(&format!("{}{}", parts.join("."), rest)).ok()
+}
+
+// Version of a package in the project: the installed one, or the lowest its range in package.json allows
+fn project_version(project_root: &Path, package: &str) -> Option<Result<Version, String>> {
+    if let Some(installed) = get_installed_version(project_root, package) {
+        return Some(Version::parse(&installed).map_err(|_| installed));
+    }
+
+    let range = get_package_version(project_root, package)?;
+    let lowest = range.trim_start_matches(['^', '~', '>', '=', ' '])
+        .split([' ', '|'])
+        .next()
+        .and_then(padded_version);
+    Some(lowest.ok_or(range))
+}
+
+// npm ranges in semver's syntax, which joins comparators with commas and reads a bare
+// version as `^` where npm means that exact version
+fn version_req(range: &str) -> Option<VersionReq> {
+    let range = range.trim();
+    if let Some((low, high)) = range.split_once(" - ") {
+        return VersionReq::parse(&format!(">={}, <={}", low.trim(), high.trim())).ok();
+    }
+
+    // `>= 18` is one comparator
+    let mut comparators: Vec<String> = Vec::new();
+    let mut operator = String::new();
+    for token in range.split_whitespace() {
+        if token.chars().all(|c| "<>=~^".contains(c)) {
+            operator.push_str(token);
+            continue;
+        }
+        let token = format!("{}{}", std::mem::take(&mut operator), token);
+        if token.starts_with(|c: char| c.is_ascii_digit() || c == 'v') && !token.contains(['x', 'X', '*']) {
+            comparators.push(format!("={}", token.trim_start_matches('v')));
+        } else {
+            comparators.push(token);
+        }
+    }
+    VersionReq::parse(&comparators.join(", ")).ok()
+}
+
+// Whether the version is in an npm range like "^18 || ^19" or ">=16.8.0 <20", None if it can't be read
+fn satisfies(version: &Version, range: &str) -> Option<bool> {
+    let range = range.trim();
+    if range.is_empty() || range == "*" || range == "latest" {
+        return Some(true);
+    }
+
+    let mut readable = false;
+    for alternative in range.split("||") {
+        let Some(req) = version_req(alternative) else {
+            continue;
+        };
+        if req.matches(version) {
+            return Some(true);
+        }
+        readable = true;
+    }
+    readable.then_some(false)
+}
+
+// Check a peer dependency spec like `react@^18 || ^19` against the project
+pub fn check_peer(project_root: &Path, spec: &str) -> PeerCheck {
+    let (package, range) = split_dependency(spec);
+
+    match project_version(project_root, package) {
+        None => PeerCheck::Missing,
+        Some(Err(version)) => PeerCheck::Unknown(version),
+        Some(Ok(version)) => match satisfies(&version, range.unwrap_or("*")) {
+            Some(true) => PeerCheck::Satisfied,
+            Some(false) => PeerCheck::Mismatch(version.to_string()),
+            None => PeerCheck::Unknown(version.to_string()),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+
+    // A project dir in the temp dir, removed when dropped
+    struct TempProject(PathBuf);
+
+    impl TempProject {
+        fn new(name: &str, dependencies: &str) -> Self {
+            let path = std::env::temp_dir().join(format!("vin-ui-{}-{}", std::process::id(), name));
+            let _ = fs::remove_dir_all(&path);
+            fs::create_dir_all(&path).unwrap();
+            fs::write(path.join("package.json"), format!(r#"{{ "dependencies": {} }}"#, dependencies)).unwrap();
+            TempProject(path)
+        }
+
+        fn install(&self, package: &str, version: &str) {
+            let dir = self.0.join("node_modules").join(package);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("package.json"), format!(r#"{{ "version": "{}" }}"#, version)).unwrap();
+        }
+    }
+
+    impl Drop for TempProject {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn check(version: &str, range: &str) -> Option<bool> {
+        satisfies(&Version::parse(version).unwrap(), range)
+    }
+
+    #[test]
+    fn npm_ranges() {
+        let cases = [
+            ("18.2.0", "^18 || ^19", Some(true)),
+            ("19.0.0", "^18 || ^19", Some(true)),
+            ("17.0.2", "^18 || ^19", Some(false)),
+            ("16.8.0", ">=16.8.0 <20", Some(true)),
+            ("19.9.9", ">=16.8.0 <20", Some(true)),
+            ("20.0.0", ">=16.8.0 <20", Some(false)),
+            ("16.7.0", ">=16.8.0 <20", Some(false)),
+            ("18.3.1", "18", Some(true)),
+            ("19.0.0", "18", Some(false)),
+            ("18.3.1", "18.x", Some(true)),
+            ("17.0.0", "18.x", Some(false)),
+            ("1.2.3", "1.2.3 - 2", Some(true)),
+            ("2.9.0", "1.2.3 - 2", Some(true)),
+            ("3.0.0", "1.2.3 - 2", Some(false)),
+            ("1.2.2", "1.2.3 - 2", Some(false)),
+            ("18.0.0", ">= 18", Some(true)),
+            ("17.9.0", ">= 18", Some(false)),
+            ("0.0.1", "*", Some(true)),
+            ("1.0.0", "", Some(true)),
+            ("1.2.3", "1.2.3", Some(true)),
+            ("1.2.4", "1.2.3", Some(false)),
+            ("1.2.3", "v1.2.3", Some(true)),
+            ("1.2.3", "~1.2", Some(true)),
+            ("1.3.0", "~1.2", Some(false)),
+            ("1.0.0", "next", None),
+        ];
+        for (version, range, expected) in cases {
+            assert_eq!(check(version, range), expected, "{} in '{}'", version, range);
+        }
+    }
+
+    #[test]
+    fn padded_versions() {
+        assert_eq!(padded_version("18"), Version::parse("18.0.0").ok());
+        assert_eq!(padded_version("18.2.x"), Version::parse("18.2.0").ok());
+        assert_eq!(padded_version("v1.2"), Version::parse("1.2.0").ok());
+        assert_eq!(padded_version("19.0.0-rc.1"), Version::parse("19.0.0-rc.1").ok());
+        assert_eq!(padded_version("latest"), None);
+        assert_eq!(padded_version("1.2.3.4"), None);
+    }
+
+    #[test]
+    fn lowest_version_of_a_package_json_range() {
+        let project = TempProject::new(
+            "lowest-version",
+            r#"{ "react": "^18.2.0", "next": ">=14.1 <16", "clsx": "~2", "zod": "3.x", "tailwind-merge": "latest" }"#,
+        );
+        let version = |package: &str| project_version(&project.0, package);
+
+        assert_eq!(version("react"), Some(Ok(Version::new(18, 2, 0))));
+        assert_eq!(version("next"), Some(Ok(Version::new(14, 1, 0))));
+        assert_eq!(version("clsx"), Some(Ok(Version::new(2, 0, 0))));
+        assert_eq!(version("zod"), Some(Ok(Version::new(3, 0, 0))));
+        assert_eq!(version("tailwind-merge"), Some(Err("latest".to_string())));
+        assert_eq!(version("vue"), None);
+
+        // what's installed is exact, and wins over the range
+        project.install("react", "18.3.1");
+        assert_eq!(version("react"), Some(Ok(Version::new(18, 3, 1))));
+    }
+
+    #[test]
+    fn peer_checks() {
+        let project = TempProject::new("peer-checks", r#"{ "react": "^18.2.0", "next": "canary" }"#);
+
+        assert!(matches!(check_peer(&project.0, "react@>=18"), PeerCheck::Satisfied));
+        assert!(matches!(check_peer(&project.0, "react"), PeerCheck::Satisfied));
+        assert!(matches!(check_peer(&project.0, "react@^19"), PeerCheck::Mismatch(version) if version == "18.2.0"));
+        assert!(matches!(check_peer(&project.0, "next@>=14"), PeerCheck::Unknown(range) if range == "canary"));
+        assert!(matches!(check_peer(&project.0, "vue@^3"), PeerCheck::Missing));
+    }
+}
@@ -0,0 +1,80 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::Result;
+
+use crate::models::component::ComponentConfig;
+use crate::utils::component::load_component_config;
+
+pub const HOOKS_DIR: &str = "hooks";
+
+// Hooks live in templates/hooks as `use-<name>.ts` (or `.tsx`), with an
+// optional `use-<name>.json` config for the packages they need
+pub fn is_hook_name(name: &str) -> bool {
+    name.starts_with("use-")
+}
+
+pub fn is_hook(template_dir: &Path, hook_name: &str) -> bool {
+    is_hook_name(hook_name)
+        && ["ts", "tsx"].iter().any(|ext| {
+            template_dir.join(HOOKS_DIR).join(format!("{}.{}", hook_name, ext)).is_file()
+        })
+}
+
+// Entry file of a hook, relative to the templates dir
+pub fn hook_entry_file(template_dir: &Path, hook_name: &str) -> String {
+    let tsx = format!("{}/{}.tsx", HOOKS_DIR, hook_name);
+    if template_dir.join(&tsx).is_file() {
+        tsx
+    } else {
+        format!("{}/{}.ts", HOOKS_DIR, hook_name)
+    }
+}
+
+// Config file of a hook, relative to the templates dir
+pub fn hook_config_file(hook_name: &str) -> String {
+    format!("{}/{}.json", HOOKS_DIR, hook_name)
+}
+
+pub fn load_hook_config(template_dir: &Path, hook_name: &str) -> Result<Option<ComponentConfig>> {
+    load_component_config(&template_dir.join(hook_config_file(hook_name)))
+}
+
+// Every template file (relative to the templates dir) installing a hook reads
+pub fn hook_template_files(template_dir: &Path, hook_name: &str) -> Vec<String> {
+    vec![
+        hook_entry_file(template_dir, hook_name),
+        hook_config_file(hook_name),
+    ]
+}
+
+// Lists all available hooks in the templates directory
+pub fn get_available_hooks(template_dir: &Path) -> Result<Vec<String>> {
+    let hooks_dir = template_dir.join(HOOKS_DIR);
+    if !hooks_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut hooks = Vec::new();
+    for entry in fs::read_dir(&hooks_dir)?.flatten() {
+        let path = entry.path();
+        if !path.is_file() || !path.extension().is_some_and(|ext| ext == "ts" || ext == "tsx") {
+            continue;
+        }
+        if let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) {
+            if is_hook_name(name) {
+                hooks.push(name.to_string());
+            }
+        }
+    }
+
+    hooks.sort();
+    hooks.dedup();
+    Ok(hooks)
+}
+
+// Where a hook is installed, keeping its extension
+pub fn hook_dest_path(template_dir: &Path, hooks_dir: &Path, hook_name: &str) -> PathBuf {
+    let entry = hook_entry_file(template_dir, hook_name);
+    let file_name = entry.rsplit('/').next().unwrap_or(&entry).to_string();
+    hooks_dir.join(file_name)
+}
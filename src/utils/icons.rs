@@ -0,0 +1,284 @@
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::{Result, Context, bail};
+
+use crate::models::config::ProjectConfig;
+use crate::models::icon::IconTable;
+use crate::utils::classes::is_script_file;
+use crate::utils::config::{load_user_config, PROJECT_CONFIG_FILE};
+use crate::utils::integrity::verify_template_files;
+use crate::utils::project::package_name;
+use crate::utils::template_finder::get_template_locations;
+
+pub const ICONS_FILE: &str = "icons.json";
+
+// The icon table of the first templates directory that has one
+pub fn load_icon_table() -> Result<Option<IconTable>> {
+    let Some(template_dir) = get_template_locations()
+        .into_iter()
+        .map(|location| location.path)
+        .find(|path| path.join(ICONS_FILE).is_file())
+    else {
+        return Ok(None);
+    };
+
+    verify_template_files(&template_dir, &[ICONS_FILE.to_string()], &load_user_config()?)
+        .context("Refusing to use the icon table: template verification failed")?;
+
+    let path = template_dir.join(ICONS_FILE);
+    let content = fs::read_to_string(&path)
+        .context(format!("Failed to read icon table: {}", path.display()))?;
+    let table = serde_json::from_str(&content)
+        .context(format!("Failed to parse icon table: {}", path.display()))?;
+    Ok(Some(table))
+}
+
+// An import statement, `import { A, B as C } from "module";`
+struct Import {
+    start: usize,
+    end: usize,                                 // Exclusive, including the `;`
+    names: Option<Vec<(String, String)>>,       // Imported name -> local name, None for default/namespace imports
+    module: String,
+    quote: char,
+    semicolon: bool,
+}
+
+fn parse_import(src: &str, start: usize) -> Option<Import> {
+    let mut pos = start + "import".len();
+    pos = src.len() - src[pos..].trim_start().len();
+
+    let names = if src[pos..].starts_with('{') {
+        let close = pos + src[pos..].find('}')?;
+        let names = src[pos + 1..close]
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(|name| match name.split_once(" as ") {
+                Some((imported, local)) => (imported.trim().to_string(), local.trim().to_string()),
+                None => (name.to_string(), name.to_string()),
+            })
+            .collect();
+        pos = close + 1;
+        Some(names)
+    } else {
+        None
+    };
+
+    // side effect imports (`import "./x.css"`) have no `from`, don't take the next statement's
+    let from = pos + src[pos..].find("from")?;
+    if src[pos..from].contains([';', '"', '\'']) {
+        return None;
+    }
+
+    let quote_at = src.len() - src[from + "from".len()..].trim_start().len();
+    let quote = src[quote_at..].chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    let module_end = quote_at + 1 + src[quote_at + 1..].find(quote)?;
+    let semicolon = src[module_end + 1..].starts_with(';');
+
+    Some(Import {
+        start,
+        end: module_end + 1 + usize::from(semicolon),
+        names,
+        module: src[quote_at + 1..module_end].to_string(),
+        quote,
+        semicolon,
+    })
+}
+
+fn find_imports(src: &str) -> Vec<Import> {
+    let mut imports = Vec::new();
+    let mut line_start = 0;
+    for line in src.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("import ") || trimmed.starts_with("import{") {
+            if let Some(import) = parse_import(src, line_start + line.len() - trimmed.len()) {
+                imports.push(import);
+            }
+        }
+        line_start += line.len();
+    }
+    imports
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '$'
+}
+
+// Where `name` appears as a whole word, outside the given ranges
+fn word_positions(src: &str, name: &str, skip: &[(usize, usize)]) -> Vec<usize> {
+    src.match_indices(name)
+        .map(|(at, _)| at)
+        .filter(|at| !skip.iter().any(|(start, end)| at >= start && at < end))
+        .filter(|at| {
+            let before = src[..*at].chars().next_back().is_none_or(|c| !is_ident_char(c));
+            let after = src[at + name.len()..].chars().next().is_none_or(|c| !is_ident_char(c));
+            before && after
+        })
+        .collect()
+}
+
+fn render_import(names: &[(String, String)], module: &str, quote: char, semicolon: bool) -> String {
+    let names: Vec<String> = names.iter()
+        .map(|(imported, local)| if imported == local { imported.clone() } else { format!("{} as {}", imported, local) })
+        .collect();
+    format!("import {{ {} }} from {}{}{}{}", names.join(", "), quote, module, quote, if semicolon { ";" } else { "" })
+}
+
+// Rewrites icon imports from other libraries to the project's icon library
+pub struct IconSwap {
+    table: IconTable,
+    target: String,                                             // Library from the table
+    converted: RefCell<BTreeSet<String>>,                       // Libraries icons were moved away from
+    kept: RefCell<BTreeSet<String>>,                            // Libraries still imported after the swap
+    unmapped: RefCell<BTreeMap<PathBuf, BTreeSet<String>>>,     // Icons with no counterpart, by file
+}
+
+impl IconSwap {
+    // None when the project doesn't pick an icon library
+    pub fn new(project_config: &ProjectConfig) -> Result<Option<Self>> {
+        let Some(target) = &project_config.icons else {
+            return Ok(None);
+        };
+
+        let Some(table) = load_icon_table()? else {
+            bail!("No {} found in the templates, it is needed for \"icons\" in {}", ICONS_FILE, PROJECT_CONFIG_FILE);
+        };
+        if !table.libraries.contains_key(target) {
+            bail!(
+                "Unknown icon library '{}' in {}, expected one of: {}",
+                target, PROJECT_CONFIG_FILE, table.libraries.keys().cloned().collect::<Vec<_>>().join(", ")
+            );
+        }
+
+        Ok(Some(IconSwap {
+            table,
+            target: target.clone(),
+            converted: RefCell::new(BTreeSet::new()),
+            kept: RefCell::new(BTreeSet::new()),
+            unmapped: RefCell::new(BTreeMap::new()),
+        }))
+    }
+
+    fn counterpart(&self, library: &str, icon: &str) -> Option<&String> {
+        self.table.icons.iter()
+            .find(|names| names.get(library).is_some_and(|name| name == icon))
+            .and_then(|names| names.get(&self.target))
+    }
+
+    fn swap(&self, src: &str, path: &Path) -> String {
+        let imports = find_imports(src);
+        let import_ranges: Vec<(usize, usize)> = imports.iter().map(|import| (import.start, import.end)).collect();
+        let target_module = &self.table.libraries[&self.target].import;
+
+        let mut output = String::with_capacity(src.len());
+        let mut renames = Vec::new();
+        let mut copied = 0;
+
+        for import in &imports {
+            let Some((library, _)) = self.table.libraries.iter()
+                .find(|(name, library)| library.import == import.module && **name != self.target)
+            else {
+                continue;
+            };
+
+            let Some(names) = &import.names else {
+                self.kept.borrow_mut().insert(library.clone());
+                self.unmapped.borrow_mut().entry(path.to_path_buf()).or_default()
+                    .insert(format!("{} (not a named import)", import.module));
+                continue;
+            };
+
+            let mut moved = Vec::new();
+            let mut kept = Vec::new();
+            for (imported, local) in names {
+                let Some(counterpart) = self.counterpart(library, imported) else {
+                    self.unmapped.borrow_mut().entry(path.to_path_buf()).or_default().insert(imported.clone());
+                    kept.push((imported.clone(), local.clone()));
+                    continue;
+                };
+
+                // usages are renamed when they are all JSX tags, otherwise the new icon
+                // takes the old name so every other reference keeps working
+                let uses = word_positions(src, local, &import_ranges);
+                let tags = uses.iter().filter(|at| src[..**at].ends_with('<') || src[..**at].ends_with("</")).count();
+                if imported == local && tags == uses.len() {
+                    renames.push((local.clone(), counterpart.clone()));
+                    moved.push((counterpart.clone(), counterpart.clone()));
+                } else {
+                    moved.push((counterpart.clone(), local.clone()));
+                }
+            }
+
+            let mut statements = Vec::new();
+            if !kept.is_empty() {
+                self.kept.borrow_mut().insert(library.clone());
+                statements.push(render_import(&kept, &import.module, import.quote, import.semicolon));
+            }
+            if !moved.is_empty() {
+                self.converted.borrow_mut().insert(library.clone());
+                statements.push(render_import(&moved, target_module, import.quote, import.semicolon));
+            }
+
+            output.push_str(&src[copied..import.start]);
+            output.push_str(&statements.join("\n"));
+            copied = import.end;
+        }
+        output.push_str(&src[copied..]);
+
+        // `<ArrowRight />` -> `<ArrowRightIcon />`, the imports are already renamed
+        for (old, new) in renames {
+            let imports_end = find_imports(&output).last().map(|import| import.end).unwrap_or(0);
+            let positions = word_positions(&output, &old, &[(0, imports_end)]);
+            for at in positions.into_iter().rev() {
+                output.replace_range(at..at + old.len(), &new);
+            }
+        }
+        output
+    }
+
+    // Rewrite the icon imports of an installed file in place
+    pub fn apply_to_file(&self, path: &Path) -> Result<()> {
+        if !is_script_file(path) {
+            return Ok(());
+        }
+
+        let Ok(content) = fs::read_to_string(path) else {
+            return Ok(());
+        };
+
+        let rewritten = self.swap(&content, path);
+        if rewritten != content {
+            fs::write(path, rewritten)
+                .context(format!("Failed to update icons in {}", path.display()))?;
+        }
+        Ok(())
+    }
+
+    // The dependencies with the libraries icons were moved away from replaced by the project's
+    pub fn dependencies(&self, dependencies: &[String]) -> Vec<String> {
+        let converted = self.converted.borrow();
+        let kept = self.kept.borrow();
+        let dropped: Vec<&str> = converted.iter()
+            .filter(|library| !kept.contains(*library))
+            .map(|library| self.table.libraries[library].package.as_str())
+            .collect();
+
+        let mut dependencies: Vec<String> = dependencies.iter()
+            .filter(|dependency| !dropped.contains(&package_name(dependency)))
+            .cloned()
+            .collect();
+
+        let target_package = &self.table.libraries[&self.target].package;
+        if !converted.is_empty() && !dependencies.iter().any(|dependency| package_name(dependency) == target_package) {
+            dependencies.push(target_package.clone());
+        }
+        dependencies
+    }
+
+    // Icons that stayed with their library, by file
+    pub fn unmapped(&self) -> BTreeMap<PathBuf, BTreeSet<String>> {
+        self.unmapped.borrow().clone()
+    }
+}
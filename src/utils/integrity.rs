@@ -0,0 +1,337 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use anyhow::{Result, Context};
+use ed25519_dalek::{Signature, VerifyingKey};
+use thiserror::Error;
+
+use crate::models::config::{TrustedKey, UserConfig};
+use crate::models::registry::RegistryIndex;
+use crate::utils::cache::sha256_hex;
+use crate::utils::ui::print_warning;
+
+pub const INDEX_FILE: &str = "index.json";
+pub const SIGNATURE_FILE: &str = "index.json.sig";
+
+#[derive(Debug, Error)]
+pub enum IntegrityError {
+    #[error("checksum mismatch for '{path}': expected {expected}, found {actual}")]
+    ChecksumMismatch { path: String, expected: String, actual: String },
+    #[error("'{0}' is not signed, but require_signatures is enabled in your config")]
+    MissingSignature(String),
+    #[error("signature on '{0}' does not match any trusted key")]
+    UntrustedSignature(String),
+    #[error("signature file for '{0}' is not a valid hex-encoded ed25519 signature")]
+    MalformedSignature(String),
+    #[error("'{0}' has no digest in the template index, but require_signatures is enabled in your config")]
+    Unverified(String),
+}
+
+pub struct Verification {
+    pub verified: usize,            // Files checked against a digest
+    pub signed_by: Option<String>,  // Trusted key that signed the index
+}
+
+fn parse_key(key: &TrustedKey) -> Result<VerifyingKey> {
+    let bytes: [u8; 32] = hex::decode(key.public_key.trim())
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .context(format!("Trusted key '{}' is not a hex-encoded ed25519 public key", key.name))?;
+
+    VerifyingKey::from_bytes(&bytes)
+        .context(format!("Trusted key '{}' is not a valid ed25519 public key", key.name))
+}
+
+// Check a detached signature over an index, returning the name of the key that signed it
+pub fn verify_index_signature(
+    label: &str,
+    index_bytes: &[u8],
+    signature: Option<&[u8]>,
+    config: &UserConfig,
+) -> Result<Option<String>> {
+    let signature = match signature {
+        Some(signature) => signature,
+        None if config.require_signatures => {
+            return Err(IntegrityError::MissingSignature(label.to_string()).into());
+        }
+        None => return Ok(None),
+    };
+
+    if config.trusted_keys.is_empty() && !config.require_signatures {
+        print_warning(&format!("'{}' is signed, but no trusted keys are configured to check it", label));
+        return Ok(None);
+    }
+
+    let signature: [u8; 64] = std::str::from_utf8(signature)
+        .ok()
+        .and_then(|text| hex::decode(text.trim()).ok())
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| IntegrityError::MalformedSignature(label.to_string()))?;
+    let signature = Signature::from_bytes(&signature);
+
+    for key in &config.trusted_keys {
+        if parse_key(key)?.verify_strict(index_bytes, &signature).is_ok() {
+            return Ok(Some(key.name.clone()));
+        }
+    }
+
+    Err(IntegrityError::UntrustedSignature(label.to_string()).into())
+}
+
+pub fn verify_digest(path: &str, bytes: &[u8], expected: &str) -> Result<(), IntegrityError> {
+    let actual = sha256_hex(bytes);
+    if !actual.eq_ignore_ascii_case(expected.trim()) {
+        return Err(IntegrityError::ChecksumMismatch {
+            path: path.to_string(),
+            expected: expected.to_string(),
+            actual,
+        });
+    }
+    Ok(())
+}
+
+// Verify template files (relative to the templates dir) against the index.json
+// shipped alongside them. Returns None when there is no index to check against.
+pub fn verify_template_files(template_dir: &Path, files: &[String], config: &UserConfig) -> Result<Option<Verification>> {
+    let index_path = template_dir.join(INDEX_FILE);
+    let label = index_path.display().to_string();
+
+    if !index_path.exists() {
+        if config.require_signatures {
+            return Err(IntegrityError::MissingSignature(template_dir.display().to_string()).into());
+        }
+        return Ok(None);
+    }
+
+    let index_bytes = fs::read(&index_path)?;
+    let signature_path = template_dir.join(SIGNATURE_FILE);
+    let signature = if signature_path.exists() {
+        Some(fs::read(&signature_path)?)
+    } else {
+        None
+    };
+
+    let signed_by = verify_index_signature(&label, &index_bytes, signature.as_deref(), config)?;
+
+    let index: RegistryIndex = serde_json::from_slice(&index_bytes)
+        .context(format!("Failed to parse template index: {}", label))?;
+
+    let digests: HashMap<&str, &str> = index.components.iter()
+        .flat_map(|entry| entry.files.iter())
+        .filter_map(|file| file.sha256.as_deref().map(|digest| (file.path.as_str(), digest)))
+        .collect();
+
+    let mut verified = 0;
+    for file in files {
+        let path = template_dir.join(file);
+        if !path.exists() {
+            // missing files are reported by the caller
+            continue;
+        }
+
+        match digests.get(file.as_str()) {
+            Some(expected) => {
+                verify_digest(file, &fs::read(&path)?, expected)?;
+                verified += 1;
+            }
+            None if config.require_signatures => {
+                return Err(IntegrityError::Unverified(file.clone()).into());
+            }
+            None => {}
+        }
+    }
+
+    Ok(Some(Verification { verified, signed_by }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    use crate::models::registry::{RegistryEntry, RegistryFile};
+
+    const BUTTON: &[u8] = b"export function Button() {}\n";
+
+    // A templates dir in the temp dir, removed when dropped
+    struct TempTemplates(PathBuf);
+
+    impl TempTemplates {
+        fn new(name: &str) -> Self {
+            let path = std::env::temp_dir().join(format!("vin-ui-{}-{}", std::process::id(), name));
+            let _ = fs::remove_dir_all(&path);
+            fs::create_dir_all(&path).unwrap();
+            fs::write(path.join("button.tsx"), BUTTON).unwrap();
+            TempTemplates(path)
+        }
+
+        // Write an index listing button.tsx with the given digest, returning its bytes
+        fn index(&self, digest: Option<String>) -> Vec<u8> {
+            let index = RegistryIndex {
+                name: None,
+                components: vec![RegistryEntry {
+                    name: "button".to_string(),
+                    description: None,
+                    tags: Vec::new(),
+                    files: vec![RegistryFile { path: "button.tsx".to_string(), sha256: digest }],
+                }],
+            };
+            let bytes = serde_json::to_vec_pretty(&index).unwrap();
+            fs::write(self.0.join(INDEX_FILE), &bytes).unwrap();
+            bytes
+        }
+
+        fn sign(&self, key: &SigningKey, index_bytes: &[u8]) {
+            fs::write(self.0.join(SIGNATURE_FILE), hex::encode(key.sign(index_bytes).to_bytes())).unwrap();
+        }
+
+        fn verify(&self, config: &UserConfig) -> Result<Option<Verification>> {
+            verify_template_files(&self.0, &["button.tsx".to_string()], config)
+        }
+    }
+
+    impl Drop for TempTemplates {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn signing_key(seed: u8) -> SigningKey {
+        SigningKey::from_bytes(&[seed; 32])
+    }
+
+    fn config(keys: &[(&str, &SigningKey)], require_signatures: bool) -> UserConfig {
+        UserConfig {
+            trusted_keys: keys.iter()
+                .map(|(name, key)| TrustedKey {
+                    name: name.to_string(),
+                    public_key: hex::encode(key.verifying_key().to_bytes()),
+                })
+                .collect(),
+            require_signatures,
+            overlays: Vec::new(),
+        }
+    }
+
+    fn integrity_error(result: Result<Option<Verification>>) -> IntegrityError {
+        match result {
+            Ok(_) => panic!("verification passed"),
+            Err(err) => err.downcast().expect("not an integrity error"),
+        }
+    }
+
+    #[test]
+    fn accepts_a_correct_digest() {
+        let templates = TempTemplates::new("digest-ok");
+        templates.index(Some(sha256_hex(BUTTON)));
+
+        let verification = templates.verify(&UserConfig::default()).unwrap().unwrap();
+        assert_eq!(verification.verified, 1);
+        assert_eq!(verification.signed_by, None);
+    }
+
+    #[test]
+    fn rejects_a_wrong_digest() {
+        let templates = TempTemplates::new("digest-wrong");
+        templates.index(Some(sha256_hex(b"something else")));
+
+        assert!(matches!(
+            integrity_error(templates.verify(&UserConfig::default())),
+            IntegrityError::ChecksumMismatch { path, .. } if path == "button.tsx"
+        ));
+    }
+
+    #[test]
+    fn no_index_is_nothing_to_check() {
+        let templates = TempTemplates::new("no-index");
+        assert!(templates.verify(&UserConfig::default()).unwrap().is_none());
+    }
+
+    #[test]
+    fn accepts_a_signature_from_a_trusted_key() {
+        let templates = TempTemplates::new("signed-trusted");
+        let key = signing_key(1);
+        let index = templates.index(Some(sha256_hex(BUTTON)));
+        templates.sign(&key, &index);
+
+        let verification = templates.verify(&config(&[("other", &signing_key(2)), ("team", &key)], true)).unwrap().unwrap();
+        assert_eq!(verification.verified, 1);
+        assert_eq!(verification.signed_by.as_deref(), Some("team"));
+    }
+
+    #[test]
+    fn rejects_a_signature_from_an_untrusted_key() {
+        let templates = TempTemplates::new("signed-untrusted");
+        let index = templates.index(Some(sha256_hex(BUTTON)));
+        templates.sign(&signing_key(3), &index);
+
+        assert!(matches!(
+            integrity_error(templates.verify(&config(&[("team", &signing_key(1))], false))),
+            IntegrityError::UntrustedSignature(_)
+        ));
+    }
+
+    #[test]
+    fn rejects_a_tampered_index() {
+        let templates = TempTemplates::new("tampered");
+        let key = signing_key(1);
+        let index = templates.index(Some(sha256_hex(b"the signed file")));
+        templates.sign(&key, &index);
+
+        // the digest is swapped for one matching a changed file, the signature no longer holds
+        templates.index(Some(sha256_hex(BUTTON)));
+        assert!(matches!(
+            integrity_error(templates.verify(&config(&[("team", &key)], false))),
+            IntegrityError::UntrustedSignature(_)
+        ));
+    }
+
+    #[test]
+    fn rejects_a_malformed_signature() {
+        let templates = TempTemplates::new("malformed");
+        templates.index(Some(sha256_hex(BUTTON)));
+        fs::write(templates.0.join(SIGNATURE_FILE), "not a signature").unwrap();
+
+        assert!(matches!(
+            integrity_error(templates.verify(&config(&[("team", &signing_key(1))], false))),
+            IntegrityError::MalformedSignature(_)
+        ));
+    }
+
+    #[test]
+    fn require_signatures_refuses_unsigned_templates() {
+        let key = signing_key(1);
+
+        let templates = TempTemplates::new("required-no-index");
+        assert!(matches!(
+            integrity_error(templates.verify(&config(&[("team", &key)], true))),
+            IntegrityError::MissingSignature(_)
+        ));
+
+        let templates = TempTemplates::new("required-unsigned");
+        templates.index(Some(sha256_hex(BUTTON)));
+        assert!(matches!(
+            integrity_error(templates.verify(&config(&[("team", &key)], true))),
+            IntegrityError::MissingSignature(_)
+        ));
+    }
+
+    #[test]
+    fn require_signatures_refuses_undigested_files() {
+        let templates = TempTemplates::new("required-undigested");
+        let key = signing_key(1);
+        let index = templates.index(None);
+        templates.sign(&key, &index);
+
+        assert!(matches!(
+            integrity_error(templates.verify(&config(&[("team", &key)], true))),
+            IntegrityError::Unverified(file) if file == "button.tsx"
+        ));
+
+        // without require_signatures an undigested file is just not counted
+        let verification = templates.verify(&config(&[("team", &key)], false)).unwrap().unwrap();
+        assert_eq!(verification.verified, 0);
+        assert_eq!(verification.signed_by.as_deref(), Some("team"));
+    }
+}
@@ -2,3 +2,23 @@ pub mod component;
 pub mod project;
 pub mod ui;
 pub mod template_finder;
+pub mod cache;
+pub mod remote;
+pub mod registry;
+pub mod config;
+pub mod integrity;
+pub mod package;
+pub mod builtin;
+pub mod template_store;
+pub mod overlay;
+pub mod render;
+pub mod hook;
+pub mod block;
+pub mod asset;
+pub mod tailwind;
+pub mod stylesheet;
+pub mod tailwind_config;
+pub mod classes;
+pub mod theme;
+pub mod icons;
+pub mod dependencies;
@@ -0,0 +1,302 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::{Result, Context, bail};
+use serde_json::{Map, Value};
+
+use crate::models::component::ComponentConfig;
+use crate::models::overlay::OverlayPatch;
+use crate::utils::cache::{prepare_staging_dir, StagingDir};
+use crate::utils::component::{component_config_file, component_template_files};
+
+pub const PATCH_SUFFIX: &str = ".overlay.json";
+
+// JSON merge patch (RFC 7386): objects are merged, null removes a key,
+// anything else (including arrays) replaces the upstream value
+fn merge_patch(target: &mut Value, patch: &Value) {
+    match patch {
+        Value::Object(patch_map) => {
+            if !target.is_object() {
+                *target = Value::Object(Map::new());
+            }
+            if let Value::Object(target_map) = target {
+                for (key, value) in patch_map {
+                    if value.is_null() {
+                        target_map.remove(key);
+                    } else {
+                        merge_patch(target_map.entry(key.clone()).or_insert(Value::Null), value);
+                    }
+                }
+            }
+        }
+        _ => *target = patch.clone(),
+    }
+}
+
+fn read_json(path: &Path) -> Result<Value> {
+    let content = fs::read_to_string(path)
+        .context(format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&content)
+        .context(format!("Failed to parse {}", path.display()))
+}
+
+fn overlay_touches(overlay: &Path, file: &str) -> bool {
+    overlay.join(file).exists() || overlay.join(format!("{}{}", file, PATCH_SUFFIX)).exists()
+}
+
+// Overlay files that affect a component, for reporting
+pub fn overlay_files_for(component_name: &str, overlays: &[PathBuf]) -> Vec<PathBuf> {
+    let candidates: Vec<String> = [
+        format!("{}.tsx", component_name),
+        format!("{}.json", component_name),
+        format!("{}/{}.tsx", component_name, component_name),
+        format!("{}/{}.json", component_name, component_name),
+    ]
+    .into_iter()
+    .flat_map(|file| [format!("{}{}", file, PATCH_SUFFIX), file])
+    .collect();
+
+    overlays.iter()
+        .flat_map(|overlay| candidates.iter().map(move |file| overlay.join(file)))
+        .filter(|path| path.exists())
+        .collect()
+}
+
+// Upstream file contents with every overlay applied in order: a file with the
+// same path replaces it, a `<file>.overlay.json` patches it in place
+fn apply_file_overlays(template_dir: &Path, file: &str, overlays: &[PathBuf]) -> Result<Option<Vec<u8>>> {
+    let upstream = template_dir.join(file);
+    let mut contents = if upstream.exists() { Some(fs::read(&upstream)?) } else { None };
+
+    for overlay in overlays {
+        let replacement = overlay.join(file);
+        if replacement.exists() {
+            contents = Some(fs::read(&replacement)?);
+        }
+
+        let patch_path = overlay.join(format!("{}{}", file, PATCH_SUFFIX));
+        if !patch_path.exists() {
+            continue;
+        }
+
+        let patches: Vec<OverlayPatch> = serde_json::from_value(read_json(&patch_path)?)
+            .context(format!("Failed to parse overlay patch: {}", patch_path.display()))?;
+        let bytes = contents.context(format!(
+            "Overlay patch {} targets '{}', which does not exist upstream",
+            patch_path.display(),
+            file
+        ))?;
+        let mut text = String::from_utf8(bytes)
+            .context(format!("Overlay patch {} targets a binary file", patch_path.display()))?;
+
+        for patch in patches {
+            if !text.contains(&patch.find) {
+                bail!(
+                    "Overlay patch {} no longer applies: '{}' not found in upstream '{}'",
+                    patch_path.display(),
+                    patch.find,
+                    file
+                );
+            }
+            text = text.replace(&patch.find, &patch.replace);
+        }
+        contents = Some(text.into_bytes());
+    }
+
+    Ok(contents)
+}
+
+// Resolve a component through the overlays. Returns None when no overlay applies
+// and the upstream templates dir is used as is, otherwise a staged copy with the
+// overlays applied.
+pub fn apply_overlays(template_dir: &Path, component_name: &str, overlays: &[PathBuf]) -> Result<Option<StagingDir>> {
+    let config_file = component_config_file(template_dir, component_name);
+
+    let upstream_config = template_dir.join(&config_file);
+    let mut config_value = if upstream_config.exists() { read_json(&upstream_config)? } else { Value::Null };
+
+    let mut touched = false;
+    for overlay in overlays {
+        let partial_config = overlay.join(&config_file);
+        if partial_config.exists() {
+            merge_patch(&mut config_value, &read_json(&partial_config)?);
+            touched = true;
+        }
+    }
+
+    let config: Option<ComponentConfig> = if config_value.is_null() {
+        None
+    } else {
+        Some(serde_json::from_value(config_value.clone())
+            .context(format!("Overlays produced an invalid config for '{}'", component_name))?)
+    };
+
+    let files: Vec<String> = component_template_files(template_dir, component_name, config.as_ref())
+        .into_iter()
+        .filter(|file| file != &config_file)
+        .collect();
+    touched |= files.iter().any(|file| overlays.iter().any(|overlay| overlay_touches(overlay, file)));
+
+    if !touched {
+        return Ok(None);
+    }
+
+    let staged = prepare_staging_dir(&format!("overlay:{}:{}", template_dir.display(), component_name))?;
+    let staging_dir = staged.path();
+
+    for file in &files {
+        if let Some(contents) = apply_file_overlays(template_dir, file, overlays)? {
+            let dest = staging_dir.join(file);
+            if let Some(parent) = dest.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(dest, contents)?;
+        }
+    }
+
+    if config.is_some() {
+        fs::write(staging_dir.join(&config_file), serde_json::to_string_pretty(&config_value)?)?;
+    }
+
+    Ok(Some(staged))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    // Upstream templates and overlay dirs in the temp dir, removed when dropped
+    struct TempDirs(PathBuf);
+
+    impl TempDirs {
+        fn new(name: &str) -> Self {
+            let path = std::env::temp_dir().join(format!("vin-ui-{}-{}", std::process::id(), name));
+            let _ = fs::remove_dir_all(&path);
+            TempDirs(path)
+        }
+
+        fn dir(&self, name: &str) -> PathBuf {
+            self.0.join(name)
+        }
+
+        fn write(&self, file: &str, contents: &str) {
+            let path = self.0.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+    }
+
+    impl Drop for TempDirs {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn patched(target: Value, patch: Value) -> Value {
+        let mut target = target;
+        merge_patch(&mut target, &patch);
+        target
+    }
+
+    fn text(contents: Option<Vec<u8>>) -> String {
+        String::from_utf8(contents.unwrap()).unwrap()
+    }
+
+    #[test]
+    fn merge_patch_null_removes_a_key() {
+        assert_eq!(
+            patched(json!({ "name": "button", "utils": ["cn"] }), json!({ "utils": null })),
+            json!({ "name": "button" })
+        );
+        // removing a key that isn't there is fine
+        assert_eq!(patched(json!({ "a": 1 }), json!({ "b": null })), json!({ "a": 1 }));
+    }
+
+    #[test]
+    fn merge_patch_merges_objects() {
+        assert_eq!(
+            patched(
+                json!({ "colors": { "primary": "#4C43CD", "accent": "pink-500" }, "name": "button" }),
+                json!({ "colors": { "primary": "#FF0066", "muted": "gray-400" } })
+            ),
+            json!({ "colors": { "primary": "#FF0066", "accent": "pink-500", "muted": "gray-400" }, "name": "button" })
+        );
+    }
+
+    #[test]
+    fn merge_patch_replaces_arrays_and_scalars() {
+        assert_eq!(
+            patched(json!({ "dependencies": ["clsx", "tailwind-merge"], "description": "a" }), json!({ "dependencies": ["clsx"], "description": "b" })),
+            json!({ "dependencies": ["clsx"], "description": "b" })
+        );
+        assert_eq!(patched(json!({ "css": "old" }), json!({ "css": { "layers": {} } })), json!({ "css": { "layers": {} } }));
+        assert_eq!(patched(json!([1, 2]), json!({ "a": 1 })), json!({ "a": 1 }));
+        assert_eq!(patched(json!({ "a": 1 }), json!([3])), json!([3]));
+    }
+
+    #[test]
+    fn upstream_is_kept_without_overlays() {
+        let dirs = TempDirs::new("overlay-none");
+        dirs.write("upstream/button.tsx", "upstream");
+        dirs.write("overlay/card.tsx", "unrelated");
+
+        let contents = apply_file_overlays(&dirs.dir("upstream"), "button.tsx", &[dirs.dir("overlay")]).unwrap();
+        assert_eq!(text(contents), "upstream");
+        assert!(apply_file_overlays(&dirs.dir("upstream"), "missing.tsx", &[dirs.dir("overlay")]).unwrap().is_none());
+    }
+
+    #[test]
+    fn overlay_file_replaces_upstream() {
+        let dirs = TempDirs::new("overlay-replace");
+        dirs.write("upstream/utils/data.ts", "upstream");
+        dirs.write("first/utils/data.ts", "first");
+        dirs.write("second/utils/data.ts", "second");
+
+        let contents = apply_file_overlays(&dirs.dir("upstream"), "utils/data.ts", &[dirs.dir("first")]).unwrap();
+        assert_eq!(text(contents), "first");
+
+        // later overlays win
+        let contents = apply_file_overlays(&dirs.dir("upstream"), "utils/data.ts", &[dirs.dir("first"), dirs.dir("second")]).unwrap();
+        assert_eq!(text(contents), "second");
+    }
+
+    #[test]
+    fn patches_apply_in_order() {
+        let dirs = TempDirs::new("overlay-patch");
+        dirs.write("upstream/button.tsx", "<button className=\"from-[#4C43CD] rounded\" />");
+        dirs.write("first/button.tsx.overlay.json", r##"[{ "find": "from-[#4C43CD]", "replace": "from-[#FF0066]" }]"##);
+        dirs.write("second/button.tsx.overlay.json", r##"[{ "find": "#FF0066", "replace": "#00FF66" }, { "find": "rounded", "replace": "rounded-full" }]"##);
+
+        let contents = apply_file_overlays(&dirs.dir("upstream"), "button.tsx", &[dirs.dir("first"), dirs.dir("second")]).unwrap();
+        assert_eq!(text(contents), "<button className=\"from-[#00FF66] rounded-full\" />");
+    }
+
+    #[test]
+    fn patch_fails_when_it_no_longer_applies() {
+        let dirs = TempDirs::new("overlay-stale");
+        dirs.write("upstream/button.tsx", "<button className=\"from-[#123456]\" />");
+        dirs.write("overlay/button.tsx.overlay.json", r##"[{ "find": "from-[#4C43CD]", "replace": "from-[#FF0066]" }]"##);
+
+        let err = apply_file_overlays(&dirs.dir("upstream"), "button.tsx", &[dirs.dir("overlay")]).unwrap_err();
+        assert!(err.to_string().contains("no longer applies: 'from-[#4C43CD]' not found"), "{}", err);
+    }
+
+    #[test]
+    fn patch_fails_without_an_upstream_file() {
+        let dirs = TempDirs::new("overlay-no-upstream");
+        fs::create_dir_all(dirs.dir("upstream")).unwrap();
+        dirs.write("overlay/button.tsx.overlay.json", r#"[{ "find": "a", "replace": "b" }]"#);
+
+        let err = apply_file_overlays(&dirs.dir("upstream"), "button.tsx", &[dirs.dir("overlay")]).unwrap_err();
+        assert!(err.to_string().contains("which does not exist upstream"), "{}", err);
+    }
+
+    #[test]
+    fn untouched_components_are_not_staged() {
+        let dirs = TempDirs::new("overlay-untouched");
+        dirs.write("upstream/button.tsx", "upstream");
+        dirs.write("overlay/card.tsx", "unrelated");
+
+        assert!(apply_overlays(&dirs.dir("upstream"), "button", &[dirs.dir("overlay")]).unwrap().is_none());
+    }
+}
@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Component, Path};
+use anyhow::{Result, Context, bail};
+use tar::{Archive, Builder, Header};
+
+use crate::models::package::{PackageManifest, PACKAGE_FORMAT_VERSION};
+use crate::models::registry::{RegistryEntry, RegistryFile, RegistryIndex};
+use crate::utils::cache::{prepare_staging_dir, sha256_hex, StagingDir};
+use crate::utils::component::{component_config_file, component_template_files, load_component_config};
+use crate::utils::integrity::INDEX_FILE;
+use crate::utils::registry::safe_join;
+
+pub const PACKAGE_EXTENSION: &str = "vinpkg";
+const MANIFEST_FILE: &str = "manifest.json";
+
+pub fn is_package(source: &str) -> bool {
+    Path::new(source).extension().is_some_and(|ext| ext == PACKAGE_EXTENSION)
+}
+
+fn append_file<W: Write>(builder: &mut Builder<W>, path: &str, bytes: &[u8]) -> Result<()> {
+    let mut header = Header::new_gnu();
+    header.set_size(bytes.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    builder.append_data(&mut header, path, bytes)
+        .context(format!("Failed to add {} to package", path))?;
+    Ok(())
+}
+
+// Pack a component and every file its config references into a .vinpkg archive
+pub fn create_package(template_dir: &Path, component_name: &str, output: &Path) -> Result<PackageManifest> {
+    let config_path = template_dir.join(component_config_file(template_dir, component_name));
+    let config = load_component_config(&config_path)?;
+
+    let mut files = Vec::new();
+    let mut contents = Vec::new();
+    for file in component_template_files(template_dir, component_name, config.as_ref()) {
+        let path = template_dir.join(&file);
+        if !path.exists() {
+            continue;
+        }
+
+        let bytes = fs::read(&path)
+            .context(format!("Failed to read {}", path.display()))?;
+        files.push(RegistryFile {
+            path: file,
+            sha256: Some(sha256_hex(&bytes)),
+        });
+        contents.push(bytes);
+    }
+
+    let manifest = PackageManifest {
+        format_version: PACKAGE_FORMAT_VERSION,
+        component: component_name.to_string(),
+        config,
+        files,
+    };
+
+    let archive = File::create(output)
+        .context(format!("Failed to create {}", output.display()))?;
+    let mut builder = Builder::new(archive);
+
+    append_file(&mut builder, MANIFEST_FILE, &serde_json::to_vec_pretty(&manifest)?)?;
+    for (file, bytes) in manifest.files.iter().zip(&contents) {
+        append_file(&mut builder, &file.path, bytes)?;
+    }
+    builder.finish()?;
+
+    Ok(manifest)
+}
+
+// The component name becomes a file name in the project, so it can't be a path
+fn is_component_name(name: &str) -> bool {
+    let mut components = Path::new(name).components();
+    matches!((components.next(), components.next()), (Some(Component::Normal(_)), None))
+}
+
+// Just the manifest of a .vinpkg, without unpacking anything
+pub fn read_package_manifest(package_path: &Path) -> Result<PackageManifest> {
+    let archive = File::open(package_path)
+        .context(format!("Failed to open package: {}", package_path.display()))?;
+    let mut archive = Archive::new(archive);
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if entry.path()?.to_string_lossy() == MANIFEST_FILE {
+            let mut bytes = Vec::new();
+            entry.read_to_end(&mut bytes)?;
+            return serde_json::from_slice(&bytes)
+                .context(format!("Failed to parse package manifest: {}", package_path.display()));
+        }
+    }
+
+    bail!("{} is not a vin-ui package: missing {}", package_path.display(), MANIFEST_FILE)
+}
+
+// Unpack a .vinpkg into a staging dir with the templates layout. The manifest's
+// digests are written out as an index.json, which catches a damaged archive but
+// comes from the package itself, so it says nothing about who made it.
+pub fn stage_package(package_path: &Path) -> Result<(StagingDir, String)> {
+    let archive = File::open(package_path)
+        .context(format!("Failed to open package: {}", package_path.display()))?;
+    let mut archive = Archive::new(archive);
+
+    let mut contents: HashMap<String, Vec<u8>> = HashMap::new();
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let path = entry.path()?.to_string_lossy().to_string();
+        let mut bytes = Vec::new();
+        entry.read_to_end(&mut bytes)?;
+        contents.insert(path, bytes);
+    }
+
+    let manifest_bytes = contents.remove(MANIFEST_FILE)
+        .context(format!("{} is not a vin-ui package: missing {}", package_path.display(), MANIFEST_FILE))?;
+    let manifest: PackageManifest = serde_json::from_slice(&manifest_bytes)
+        .context(format!("Failed to parse package manifest: {}", package_path.display()))?;
+
+    if manifest.format_version > PACKAGE_FORMAT_VERSION {
+        bail!(
+            "{} uses package format {}, this vin-ui only understands up to {}",
+            package_path.display(),
+            manifest.format_version,
+            PACKAGE_FORMAT_VERSION
+        );
+    }
+
+    if !is_component_name(&manifest.component) {
+        bail!("{} has an invalid component name '{}' in its manifest", package_path.display(), manifest.component);
+    }
+
+    let staged = prepare_staging_dir(&package_path.display().to_string())?;
+    let staging_dir = staged.path();
+
+    for file in &manifest.files {
+        let bytes = contents.get(&file.path)
+            .context(format!("Package is missing '{}' listed in its manifest", file.path))?;
+
+        let dest = safe_join(staging_dir, &file.path)?;
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&dest, bytes)?;
+    }
+
+    let index = RegistryIndex {
+        name: None,
+        components: vec![RegistryEntry {
+            name: manifest.component.clone(),
+            description: manifest.config.as_ref().and_then(|config| config.description.clone()),
+            tags: Vec::new(),
+            files: manifest.files,
+        }],
+    };
+    fs::write(staging_dir.join(INDEX_FILE), serde_json::to_vec_pretty(&index)?)?;
+
+    Ok((staged, manifest.component))
+}
@@ -1,6 +1,11 @@
 use std::path::{Path, PathBuf};
 use anyhow::Result;
 
+use crate::models::component::FileType;
+use crate::models::config::ProjectConfig;
+use crate::models::project::ProjectInfo;
+use crate::utils::tailwind::detect_tailwind;
+
 // find the root of the nextjs project..
 pub fn find_project_root() -> Option<PathBuf> { 
     //Result -> Option -> (?) early-err
@@ -25,14 +30,6 @@ pub fn find_project_root() -> Option<PathBuf> {
 }
 
 
-//CLI rootdir
-pub fn get_cli_root() -> PathBuf {
-    let exe_path = std::env::current_exe().expect("Failed to get executable path");
-    let cli_root = exe_path.parent().unwrap().to_path_buf();
-    cli_root
-}
-
-
 // ensure dir exist else create
 pub fn ensure_dir(path: &Path) -> Result<()> {
     if !path.exists() {
@@ -74,3 +71,118 @@ pub fn get_install_command(package_manager: &str) -> &'static str {
         _ => "install",
     }
 }
+
+
+// Flag marking what is installed as a dev dependency
+pub fn get_dev_flag(package_manager: &str) -> &'static str {
+    match package_manager {
+        "yarn" => "--dev",
+        "bun" => "--dev",
+        "deno" => "--dev",
+        _ => "--save-dev",
+    }
+}
+
+
+fn read_json_file(path: &Path) -> Option<serde_json::Value> {
+    let content = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+// Version range of a package in package.json, from dependencies or devDependencies
+pub fn get_package_version(project_root: &Path, package: &str) -> Option<String> {
+    let package_json = read_json_file(&project_root.join("package.json"))?;
+
+    ["dependencies", "devDependencies"].iter()
+        .filter_map(|section| package_json.get(section)?.get(package)?.as_str())
+        .map(|version| version.to_string())
+        .next()
+}
+
+// Version of a package installed in node_modules, exact unlike the range in package.json
+pub fn get_installed_version(project_root: &Path, package: &str) -> Option<String> {
+    let manifest = read_json_file(&project_root.join("node_modules").join(package).join("package.json"))?;
+    manifest.get("version")?.as_str().map(|version| version.to_string())
+}
+
+// Package name and version range of a dependency spec, `lucide-react@^0.400` ->
+// (`lucide-react`, `^0.400`), `@heroicons/react` -> (`@heroicons/react`, None)
+pub fn split_dependency(dependency: &str) -> (&str, Option<&str>) {
+    let version_at = match dependency.strip_prefix('@') {
+        Some(scoped) => scoped.find('@').map(|at| at + 1),
+        None => dependency.find('@'),
+    };
+    match version_at {
+        Some(at) => (&dependency[..at], Some(&dependency[at + 1..])),
+        None => (dependency, None),
+    }
+}
+
+pub fn package_name(dependency: &str) -> &str {
+    split_dependency(dependency).0
+}
+
+// Major version out of a range like "^3.4.1", "~4.0.0" or "4"
+pub fn parse_major_version(range: &str) -> Option<u32> {
+    let digits: String = range
+        .trim_start_matches(|c: char| !c.is_ascii_digit())
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+    digits.parse().ok()
+}
+
+// Import alias from the "paths" of tsconfig.json / jsconfig.json, e.g. "@/*" -> "@"
+fn detect_import_alias(project_root: &Path) -> Option<String> {
+    ["tsconfig.json", "jsconfig.json"].iter()
+        .filter_map(|file| read_json_file(&project_root.join(file)))
+        .filter_map(|config| {
+            let paths = config.get("compilerOptions")?.get("paths")?.as_object()?.clone();
+            paths.keys()
+                .find_map(|key| key.strip_suffix("/*").map(|alias| alias.to_string()))
+        })
+        .next()
+}
+
+pub fn detect_project_info(project_root: &Path) -> ProjectInfo {
+    // the router living under src/ is what tells us the project uses a src dir
+    let src_dir = project_root.join("src").join("app").is_dir() || project_root.join("src").join("pages").is_dir();
+    let has_dir = |name: &str| project_root.join(name).is_dir() || project_root.join("src").join(name).is_dir();
+
+    ProjectInfo {
+        typescript: project_root.join("tsconfig.json").exists(),
+        src_dir,
+        app_router: has_dir("app"),
+        pages_router: has_dir("pages"),
+        tailwind: detect_tailwind(project_root),
+        alias: detect_import_alias(project_root).unwrap_or_else(|| "@".to_string()),
+    }
+}
+
+
+// Where files of a given type go, relative to the project root
+fn default_target_dir(file_type: FileType, project: &ProjectInfo) -> String {
+    let router_dir = |dir: &str| {
+        if project.src_dir { format!("src/{}", dir) } else { dir.to_string() }
+    };
+
+    match file_type {
+        FileType::Component | FileType::Style => "components/ui".to_string(),
+        FileType::Lib => "lib".to_string(),
+        FileType::Hook => "hooks".to_string(),
+        FileType::Asset => "public".to_string(),
+        FileType::Page if project.pages_router && !project.app_router => router_dir("pages"),
+        FileType::Page => router_dir("app"),
+        FileType::ApiRoute if project.pages_router && !project.app_router => router_dir("pages/api"),
+        FileType::ApiRoute => router_dir("app/api"),
+        FileType::Config => ".".to_string(),
+    }
+}
+
+// Target directory for a file type: the project's vin-ui.json paths, or the default
+pub fn get_target_dir(project_root: &Path, project: &ProjectInfo, config: &ProjectConfig, file_type: FileType) -> PathBuf {
+    let relative = config.paths.get(&file_type)
+        .cloned()
+        .unwrap_or_else(|| default_target_dir(file_type, project));
+    project_root.join(relative)
+}
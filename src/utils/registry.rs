@@ -0,0 +1,59 @@
+use std::path::{Component, Path, PathBuf};
+use anyhow::{Result, Context, bail};
+
+use crate::models::config::UserConfig;
+use crate::models::registry::{RegistryIndex, RegistryEntry};
+use crate::utils::integrity::{verify_index_signature, INDEX_FILE, SIGNATURE_FILE};
+use crate::utils::remote::{fetch, fetch_optional};
+
+// A registry is addressed either by its root URL or by its index.json URL
+pub fn split_registry_url(url: &str) -> (String, String) {
+    let url = url.trim_end_matches('/');
+    match url.strip_suffix(&format!("/{}", INDEX_FILE)) {
+        Some(base) => (base.to_string(), url.to_string()),
+        None => (url.to_string(), format!("{}/{}", url, INDEX_FILE)),
+    }
+}
+
+pub struct FetchedRegistry {
+    pub base_url: String,
+    pub index: RegistryIndex,
+    pub index_bytes: Vec<u8>,         // Raw index, as covered by the signature
+    pub signature: Option<Vec<u8>>,
+    pub signed_by: Option<String>,
+}
+
+// Fetch a registry index and check its signature against the trusted keys
+pub fn fetch_registry_index(url: &str, offline: bool, config: &UserConfig) -> Result<FetchedRegistry> {
+    let (base_url, index_url) = split_registry_url(url);
+    let index_bytes = fetch(&index_url, offline)?;
+    let signature = fetch_optional(&format!("{}/{}", base_url, SIGNATURE_FILE), offline)?;
+
+    let signed_by = verify_index_signature(&index_url, &index_bytes, signature.as_deref(), config)?;
+
+    let index: RegistryIndex = serde_json::from_slice(&index_bytes)
+        .context(format!("Failed to parse registry index: {}", index_url))?;
+
+    Ok(FetchedRegistry { base_url, index, index_bytes, signature, signed_by })
+}
+
+// Keep entries matching any of the names or tags, everything when no filter is given
+pub fn filter_entries(index: &RegistryIndex, names: &[String], tags: &[String]) -> Vec<RegistryEntry> {
+    index.components.iter()
+        .filter(|entry| {
+            (names.is_empty() && tags.is_empty())
+                || names.iter().any(|name| name == &entry.name)
+                || entry.tags.iter().any(|tag| tags.contains(tag))
+        })
+        .cloned()
+        .collect()
+}
+
+// Registry file paths must stay inside the destination directory
+pub fn safe_join(root: &Path, relative: &str) -> Result<PathBuf> {
+    let path = Path::new(relative);
+    if path.components().any(|c| !matches!(c, Component::Normal(_))) {
+        bail!("Refusing registry file path outside the templates layout: {}", relative);
+    }
+    Ok(root.join(path))
+}
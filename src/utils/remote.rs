@@ -0,0 +1,113 @@
+use std::fs;
+use anyhow::{Result, Context, bail};
+
+use crate::models::component::ComponentConfig;
+use crate::utils::cache::{self, prepare_staging_dir, StagingDir};
+use crate::utils::component::{component_template_files, is_glob};
+use crate::utils::integrity::{INDEX_FILE, SIGNATURE_FILE};
+use crate::utils::registry::safe_join;
+use crate::utils::ui::print_warning;
+
+pub fn is_remote(source: &str) -> bool {
+    source.starts_with("http://") || source.starts_with("https://")
+}
+
+// GET a URL, a 404 is not an error
+fn download(url: &str) -> Result<Option<Vec<u8>>> {
+    match ureq::get(url).call() {
+        Ok(mut response) => {
+            let bytes = response.body_mut().read_to_vec()
+                .context(format!("Failed to read response from {}", url))?;
+            Ok(Some(bytes))
+        }
+        Err(ureq::Error::StatusCode(404)) => Ok(None),
+        Err(err) => Err(err).context(format!("Failed to fetch {}", url)),
+    }
+}
+
+// Fetch through the cache. Offline only the cache is consulted, online a
+// failed request falls back to the cached copy when there is one.
+pub fn fetch_optional(url: &str, offline: bool) -> Result<Option<Vec<u8>>> {
+    if offline {
+        return cache::lookup(url);
+    }
+
+    match download(url) {
+        Ok(Some(bytes)) => {
+            cache::store(url, &bytes)?;
+            Ok(Some(bytes))
+        }
+        Ok(None) => Ok(None),
+        Err(err) => match cache::lookup(url)? {
+            Some(bytes) => {
+                print_warning(&format!("{:#}, using cached copy", err));
+                Ok(Some(bytes))
+            }
+            None => Err(err),
+        },
+    }
+}
+
+pub fn fetch(url: &str, offline: bool) -> Result<Vec<u8>> {
+    match fetch_optional(url, offline)? {
+        Some(bytes) => Ok(bytes),
+        None if offline => bail!("'{}' is not in the offline cache", url),
+        None => bail!("'{}' was not found", url),
+    }
+}
+
+// Download a remote component (and the files its config references) into a
+// local directory with the same layout as the templates directory.
+pub fn stage_remote_component(url: &str, offline: bool) -> Result<(StagingDir, String)> {
+    let (base_url, file_name) = url.rsplit_once('/')
+        .context(format!("Invalid component URL: {}", url))?;
+    let component_name = file_name.strip_suffix(".tsx")
+        .context(format!("Remote component must point to a .tsx file: {}", url))?
+        .to_string();
+
+    let staged = prepare_staging_dir(url)?;
+    let staging_dir = staged.path();
+
+    let component = fetch(url, offline)?;
+    fs::write(staging_dir.join(file_name), component)?;
+
+    let config_url = format!("{}/{}.json", base_url, component_name);
+    let config = match fetch_optional(&config_url, offline)? {
+        Some(config_bytes) => {
+            let config: ComponentConfig = serde_json::from_slice(&config_bytes)
+                .context(format!("Failed to parse config file: {}", config_url))?;
+            // a URL can't be listed, so there's nothing to match a glob against
+            if let Some(file) = config.additional_files.iter().flatten().find(|file| is_glob(&file.source)) {
+                bail!(
+                    "Remote component '{}' uses the glob '{}' in additional_files, remote configs must list each file",
+                    component_name, file.source
+                );
+            }
+            fs::write(staging_dir.join(format!("{}.json", component_name)), &config_bytes)?;
+            Some(config)
+        }
+        None => None,
+    };
+
+    // the index and its signature come along when the source publishes them,
+    // so the staged files can be verified like any other template
+    let mut files = component_template_files(staging_dir, &component_name, config.as_ref());
+    files.push(INDEX_FILE.to_string());
+    files.push(SIGNATURE_FILE.to_string());
+
+    for file in files {
+        let dest = safe_join(staging_dir, &file)?;
+        if dest.exists() {
+            continue;
+        }
+
+        if let Some(bytes) = fetch_optional(&format!("{}/{}", base_url, file), offline)? {
+            if let Some(parent) = dest.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(dest, bytes)?;
+        }
+    }
+
+    Ok((staged, component_name))
+}
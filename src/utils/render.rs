@@ -0,0 +1,285 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use anyhow::{Result, Context, bail};
+
+use crate::models::component::TemplateVariable;
+use crate::models::project::ProjectInfo;
+use crate::utils::ui::{input, print_warning};
+
+// Variable name -> value. Booleans are "true" / "false".
+pub type TemplateContext = BTreeMap<String, String>;
+
+enum Tag {
+    Var(String),
+    If(String),
+    Else,
+    EndIf,
+}
+
+struct Frame {
+    parent_active: bool,
+    condition: bool,
+    in_else: bool,
+}
+
+fn parse_tag(inner: &str) -> Result<Tag> {
+    let inner = inner.trim();
+
+    if let Some(name) = inner.strip_prefix('=') {
+        return Ok(Tag::Var(name.trim().to_string()));
+    }
+
+    match inner {
+        "else" => Ok(Tag::Else),
+        "endif" => Ok(Tag::EndIf),
+        _ => match inner.strip_prefix("if ") {
+            Some(condition) => Ok(Tag::If(condition.trim().to_string())),
+            None => bail!("Unknown template tag '{{% {} %}}'", inner),
+        },
+    }
+}
+
+fn lookup<'a>(context: &'a TemplateContext, name: &str) -> Result<&'a str> {
+    context.get(name)
+        .map(|value| value.as_str())
+        .context(format!("Unknown template variable '{}'", name))
+}
+
+fn is_truthy(value: &str) -> bool {
+    !value.is_empty() && value != "false" && value != "0"
+}
+
+// `fact`, `not fact`, `var == value` or `var != value`
+fn eval_condition(condition: &str, context: &TemplateContext) -> Result<bool> {
+    if let Some(rest) = condition.strip_prefix("not ") {
+        return Ok(!eval_condition(rest.trim(), context)?);
+    }
+
+    let unquote = |value: &str| value.trim().trim_matches(|c| c == '"' || c == '\'').to_string();
+
+    if let Some((name, value)) = condition.split_once("==") {
+        return Ok(lookup(context, name.trim())? == unquote(value));
+    }
+    if let Some((name, value)) = condition.split_once("!=") {
+        return Ok(lookup(context, name.trim())? != unquote(value));
+    }
+
+    Ok(is_truthy(lookup(context, condition)?))
+}
+
+// Render `{%= var %}` placeholders and `{% if cond %}…{% else %}…{% endif %}`
+// sections. `{%` never appears in JSX, TS or CSS, so it can't clash with `{{ }}`.
+pub fn render_template(source: &str, context: &TemplateContext) -> Result<String> {
+    let mut output = String::with_capacity(source.len());
+    let mut stack: Vec<Frame> = Vec::new();
+    let mut rest = source;
+    let mut at_line_start = true;
+
+    let is_active = |stack: &[Frame]| {
+        stack.last().is_none_or(|frame| frame.parent_active && frame.condition != frame.in_else)
+    };
+
+    while let Some(start) = rest.find("{%") {
+        let end = rest[start..].find("%}")
+            .map(|offset| start + offset)
+            .context("Unclosed '{%' in template")?;
+        let tag = parse_tag(&rest[start + 2..end])?;
+
+        let mut before = &rest[..start];
+        let mut next = end + 2;
+
+        // block tags alone on their line don't leave an empty line behind
+        if !matches!(tag, Tag::Var(_)) {
+            let (line_start, starts_line) = match before.rfind('\n') {
+                Some(index) => (index + 1, true),
+                None => (0, at_line_start),
+            };
+            let after = &rest[next..];
+            let line_end = after.find('\n');
+            let trailing = &after[..line_end.unwrap_or(after.len())];
+
+            if starts_line && before[line_start..].trim().is_empty() && trailing.trim().is_empty() {
+                before = &before[..line_start];
+                next += line_end.map_or(after.len(), |index| index + 1);
+            }
+        }
+
+        let active = is_active(&stack);
+        if active {
+            output.push_str(before);
+        }
+
+        match tag {
+            Tag::Var(name) => {
+                if active {
+                    output.push_str(lookup(context, &name)?);
+                }
+            }
+            Tag::If(condition) => {
+                let condition = eval_condition(&condition, context)?;
+                stack.push(Frame { parent_active: active, condition, in_else: false });
+            }
+            Tag::Else => match stack.last_mut() {
+                Some(frame) if !frame.in_else => frame.in_else = true,
+                _ => bail!("'{{% else %}}' without a matching '{{% if %}}'"),
+            },
+            Tag::EndIf => {
+                if stack.pop().is_none() {
+                    bail!("'{{% endif %}}' without a matching '{{% if %}}'");
+                }
+            }
+        }
+
+        at_line_start = rest[..next].ends_with('\n');
+        rest = &rest[next..];
+    }
+
+    if !stack.is_empty() {
+        bail!("'{{% if %}}' without a matching '{{% endif %}}'");
+    }
+    output.push_str(rest);
+
+    Ok(output)
+}
+
+// Variables available to templates: project facts, alias paths and the
+// variables the component declares (prompted for when asked to)
+pub fn build_template_context(component_name: &str, project: &ProjectInfo, variables: &[TemplateVariable]) -> TemplateContext {
+    let mut context = TemplateContext::new();
+
+    for variable in variables {
+        let default = variable.default.clone().unwrap_or_default();
+        let value = if variable.prompt.unwrap_or(false) {
+            input(variable.description.as_deref().unwrap_or(&variable.name), &default)
+        } else {
+            default
+        };
+        context.insert(variable.name.clone(), value);
+    }
+
+    let facts = [
+        ("component_name", component_name.to_string()),
+        ("typescript", project.typescript.to_string()),
+        ("src_dir", project.src_dir.to_string()),
+        ("app_router", project.app_router.to_string()),
+        ("pages_router", project.pages_router.to_string()),
+        ("tailwind", project.tailwind.is_some().to_string()),
+        ("tailwind_version", project.tailwind_version().map(|v| v.to_string()).unwrap_or_default()),
+        ("alias", project.alias.clone()),
+        ("components_alias", format!("{}/components", project.alias)),
+        ("ui_alias", format!("{}/components/ui", project.alias)),
+        ("lib_alias", format!("{}/lib", project.alias)),
+        ("utils_alias", format!("{}/lib/utils", project.alias)),
+    ];
+
+    for (name, value) in facts {
+        if context.insert(name.to_string(), value).is_some() {
+            print_warning(&format!("Template variable '{}' is reserved for a project fact and was ignored", name));
+        }
+    }
+
+    context
+}
+
+// Copy a template file into the project, rendering it when it is text
+pub fn install_template_file(source: &Path, dest: &Path, context: &TemplateContext) -> Result<()> {
+    let bytes = fs::read(source)
+        .context(format!("Failed to read {}", source.display()))?;
+
+    let contents = match String::from_utf8(bytes) {
+        Ok(text) => render_template(&text, context)
+            .context(format!("Failed to render template {}", source.display()))?
+            .into_bytes(),
+        Err(err) => err.into_bytes(),
+    };
+
+    fs::write(dest, contents)
+        .context(format!("Failed to copy {} to {}", source.display(), dest.display()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(values: &[(&str, &str)]) -> TemplateContext {
+        values.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect()
+    }
+
+    fn render(source: &str, values: &[(&str, &str)]) -> String {
+        render_template(source, &context(values)).unwrap()
+    }
+
+    fn render_error(source: &str, values: &[(&str, &str)]) -> String {
+        format!("{:#}", render_template(source, &context(values)).unwrap_err())
+    }
+
+    #[test]
+    fn text_without_tags_passes_through() {
+        let source = "import { cn } from \"@/lib/utils\";\r\n\nconst a = { b: `${c}` };\n  {{ jsx }}  \t\n<div className={cn(\"p-2\")} />";
+        assert_eq!(render(source, &[]), source);
+        assert_eq!(render("", &[]), "");
+        assert_eq!(render("no newline at the end", &[]), "no newline at the end");
+    }
+
+    #[test]
+    fn fills_in_placeholders() {
+        let values = [("utils_alias", "@/lib/utils"), ("component_name", "button")];
+        assert_eq!(
+            render("import { cn } from \"{%= utils_alias %}\";\n// {%=component_name%}: {%= component_name %}\n", &values),
+            "import { cn } from \"@/lib/utils\";\n// button: button\n"
+        );
+    }
+
+    #[test]
+    fn conditionals_on_their_own_lines_leave_no_blank_lines() {
+        let source = "a\n{% if typescript %}\ntype Props = {};\n{% else %}\n// no types\n{% endif %}\nb\n";
+        assert_eq!(render(source, &[("typescript", "true")]), "a\ntype Props = {};\nb\n");
+        assert_eq!(render(source, &[("typescript", "false")]), "a\n// no types\nb\n");
+    }
+
+    #[test]
+    fn inline_conditionals() {
+        let source = "const x = {% if src_dir %}\"src\"{% else %}\"root\"{% endif %};";
+        assert_eq!(render(source, &[("src_dir", "true")]), "const x = \"src\";");
+        assert_eq!(render(source, &[("src_dir", "")]), "const x = \"root\";");
+    }
+
+    #[test]
+    fn nested_and_negated_conditionals() {
+        let source = "{% if app_router %}\napp\n{% if not typescript %}\njs\n{% else %}\nts\n{% endif %}\n{% else %}\npages\n{% if tailwind_version == 4 %}\nv4\n{% endif %}\n{% endif %}\n";
+        let render_with = |app, ts, tw| render(source, &[("app_router", app), ("typescript", ts), ("tailwind_version", tw)]);
+
+        assert_eq!(render_with("true", "false", "4"), "app\njs\n");
+        assert_eq!(render_with("true", "true", "4"), "app\nts\n");
+        assert_eq!(render_with("false", "true", "4"), "pages\nv4\n");
+        assert_eq!(render_with("false", "true", "3"), "pages\n");
+        assert_eq!(
+            render("{% if not not a %}x{% endif %}{% if b != 'v4' %}y{% endif %}", &[("a", "1"), ("b", "v3")]),
+            "xy"
+        );
+    }
+
+    #[test]
+    fn placeholders_in_skipped_sections_are_not_filled() {
+        assert_eq!(render("{% if tailwind %}{%= missing %}{% endif %}ok", &[("tailwind", "false")]), "ok");
+    }
+
+    #[test]
+    fn unknown_variables_are_errors() {
+        assert!(render_error("{%= missing %}", &[]).contains("Unknown template variable 'missing'"));
+        assert!(render_error("{% if missing %}x{% endif %}", &[]).contains("Unknown template variable 'missing'"));
+        assert!(render_error("{% if missing == 'a' %}x{% endif %}", &[]).contains("Unknown template variable 'missing'"));
+    }
+
+    #[test]
+    fn unterminated_and_unmatched_tags_are_errors() {
+        assert!(render_error("const a = 1; {%= alias", &[("alias", "@")]).contains("Unclosed '{%'"));
+        assert!(render_error("{% if a %}x", &[("a", "true")]).contains("without a matching '{% endif %}'"));
+        assert!(render_error("x{% endif %}", &[]).contains("'{% endif %}' without a matching '{% if %}'"));
+        assert!(render_error("x{% else %}", &[]).contains("'{% else %}' without a matching '{% if %}'"));
+        assert!(render_error("{% if a %}{% else %}{% else %}{% endif %}", &[("a", "1")]).contains("'{% else %}' without"));
+        assert!(render_error("{% for x in y %}", &[]).contains("Unknown template tag"));
+    }
+}
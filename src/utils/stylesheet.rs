@@ -0,0 +1,262 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::{Result, Context};
+
+use crate::models::component::ComponentCss;
+use crate::models::config::ProjectConfig;
+use crate::utils::tailwind::find_global_css;
+
+// CSS a component adds to the global stylesheet sits between these markers,
+// so adding it again replaces it and removing the component strips it
+fn start_marker(component_name: &str) -> String {
+    format!("/* vin-ui: {} */", component_name)
+}
+
+fn end_marker(component_name: &str) -> String {
+    format!("/* vin-ui: end {} */", component_name)
+}
+
+// The configured stylesheet (`css` in vin-ui.json), or the detected globals.css
+pub fn resolve_global_css(project_root: &Path, config: &ProjectConfig) -> Option<PathBuf> {
+    match &config.css {
+        Some(css) => Some(project_root.join(css)),
+        None => find_global_css(project_root),
+    }
+}
+
+// The stylesheet without a component's block, and where the block was
+fn split_block(content: &str, component_name: &str) -> (String, Option<usize>) {
+    let start = start_marker(component_name);
+    let end = end_marker(component_name);
+
+    let Some(start_at) = content.find(&start) else {
+        return (content.to_string(), None);
+    };
+    let end_at = content[start_at..].find(&end)
+        .map(|offset| start_at + offset + end.len())
+        .unwrap_or(content.len());
+    let end_at = if content[end_at..].starts_with('\n') { end_at + 1 } else { end_at };
+
+    let mut rest = content[..start_at].to_string();
+    rest.push_str(&content[end_at..]);
+    (rest, Some(start_at))
+}
+
+fn indent(css: &str) -> String {
+    css.trim()
+        .lines()
+        .map(|line| if line.trim().is_empty() { String::new() } else { format!("  {}", line.trim_end()) })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// Render a component's CSS, leaving out variables and keyframes the rest of the
+// stylesheet already defines, so the user's own values win
+fn render_block(component_name: &str, css: &ComponentCss, rest: &str) -> Option<String> {
+    let mut sections = Vec::new();
+
+    // :root goes first, `.dark` and the like override it with the same specificity
+    let mut selectors: Vec<_> = css.vars.iter().flatten().collect();
+    selectors.sort_by_key(|(selector, _)| selector.as_str() != ":root");
+
+    for (selector, vars) in selectors {
+        let declarations: Vec<String> = vars.iter()
+            .filter(|(name, _)| !rest.contains(&format!("{}:", name)))
+            .map(|(name, value)| format!("  {}: {};", name, value))
+            .collect();
+        if !declarations.is_empty() {
+            sections.push(format!("{} {{\n{}\n}}", selector, declarations.join("\n")));
+        }
+    }
+
+    for (name, body) in css.keyframes.iter().flatten() {
+        if !rest.contains(&format!("@keyframes {} ", name)) && !rest.contains(&format!("@keyframes {}{{", name)) {
+            sections.push(format!("@keyframes {} {{\n{}\n}}", name, indent(body)));
+        }
+    }
+
+    for (layer, rules) in css.layers.iter().flatten() {
+        sections.push(format!("@layer {} {{\n{}\n}}", layer, indent(rules)));
+    }
+
+    if sections.is_empty() {
+        return None;
+    }
+
+    Some(format!(
+        "{}\n{}\n{}\n",
+        start_marker(component_name),
+        sections.join("\n\n"),
+        end_marker(component_name)
+    ))
+}
+
+// Merge a component's CSS into the stylesheet, replacing what an earlier add put
+// there. Returns whether the file changed.
+pub fn merge_component_css(stylesheet: &Path, component_name: &str, css: &ComponentCss) -> Result<bool> {
+    let content = fs::read_to_string(stylesheet)
+        .context(format!("Failed to read {}", stylesheet.display()))?;
+
+    let (rest, position) = split_block(&content, component_name);
+    let block = render_block(component_name, css, &rest).unwrap_or_default();
+
+    let merged = match position {
+        Some(at) => format!("{}{}{}", &rest[..at], block, &rest[at..]),
+        None if block.is_empty() => rest,
+        None if rest.is_empty() => block,
+        None if rest.ends_with('\n') => format!("{}\n{}", rest, block),
+        None => format!("{}\n\n{}", rest, block),
+    };
+
+    if merged == content {
+        return Ok(false);
+    }
+
+    fs::write(stylesheet, merged)
+        .context(format!("Failed to write {}", stylesheet.display()))?;
+    Ok(true)
+}
+
+// Strip a component's CSS from the stylesheet. Returns whether there was any.
+pub fn strip_component_css(stylesheet: &Path, component_name: &str) -> Result<bool> {
+    let content = fs::read_to_string(stylesheet)
+        .context(format!("Failed to read {}", stylesheet.display()))?;
+
+    let (mut rest, position) = split_block(&content, component_name);
+    let Some(at) = position else {
+        return Ok(false);
+    };
+
+    // don't leave the blank line that separated the block behind, the rest
+    // is written back as it was before the merge
+    if rest[..at].ends_with("\n\n") {
+        rest.remove(at - 1);
+    }
+    fs::write(stylesheet, rest)
+        .context(format!("Failed to write {}", stylesheet.display()))?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    // A stylesheet in the temp dir, removed when dropped
+    struct TempStylesheet(PathBuf);
+
+    impl TempStylesheet {
+        fn new(name: &str, content: &str) -> Self {
+            let path = std::env::temp_dir().join(format!("vin-ui-{}-{}.css", std::process::id(), name));
+            fs::write(&path, content).unwrap();
+            TempStylesheet(path)
+        }
+
+        fn read(&self) -> String {
+            fs::read_to_string(&self.0).unwrap()
+        }
+
+        fn merge(&self, component_name: &str, css: &ComponentCss) -> bool {
+            merge_component_css(&self.0, component_name, css).unwrap()
+        }
+
+        fn strip(&self, component_name: &str) -> bool {
+            strip_component_css(&self.0, component_name).unwrap()
+        }
+    }
+
+    impl Drop for TempStylesheet {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.0);
+        }
+    }
+
+    const GLOBALS: &str = "@tailwind base;\n@tailwind components;\n\n:root {\n  --background: #fff;\n}\n";
+
+    fn css(value: serde_json::Value) -> ComponentCss {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn timeline_css(color: &str) -> ComponentCss {
+        css(json!({
+            "vars": { ".dark": { "--timeline-line": "#111" }, ":root": { "--timeline-line": color, "--background": "#000" } },
+            "keyframes": { "pulse-dot": "0% { opacity: 1; }\n100% { opacity: 0; }" },
+            "layers": { "components": ".om-timeline { position: relative; }" }
+        }))
+    }
+
+    fn underline_css() -> ComponentCss {
+        css(json!({ "layers": { "utilities": ".neon-underline { text-decoration: underline; }" } }))
+    }
+
+    #[test]
+    fn merges_between_markers() {
+        let stylesheet = TempStylesheet::new("merge", GLOBALS);
+        assert!(stylesheet.merge("neon-timeline", &timeline_css("#4C43CD")));
+
+        let merged = stylesheet.read();
+        assert!(merged.starts_with(GLOBALS));
+        assert_eq!(
+            &merged[GLOBALS.len()..],
+            "\n/* vin-ui: neon-timeline */\n:root {\n  --timeline-line: #4C43CD;\n}\n\n.dark {\n  --timeline-line: #111;\n}\n\n\
+             @keyframes pulse-dot {\n  0% { opacity: 1; }\n  100% { opacity: 0; }\n}\n\n\
+             @layer components {\n  .om-timeline { position: relative; }\n}\n/* vin-ui: end neon-timeline */\n"
+        );
+        // the project's own --background wins
+        assert_eq!(merged.matches("--background").count(), 1);
+    }
+
+    #[test]
+    fn merging_twice_changes_nothing() {
+        let stylesheet = TempStylesheet::new("merge-twice", GLOBALS);
+        assert!(stylesheet.merge("neon-timeline", &timeline_css("#4C43CD")));
+        let once = stylesheet.read();
+
+        assert!(!stylesheet.merge("neon-timeline", &timeline_css("#4C43CD")));
+        assert_eq!(stylesheet.read(), once);
+    }
+
+    #[test]
+    fn merging_again_only_updates_that_block() {
+        let stylesheet = TempStylesheet::new("merge-update", GLOBALS);
+        stylesheet.merge("neon-timeline", &timeline_css("#4C43CD"));
+        stylesheet.merge("neon-underline", &underline_css());
+        fs::write(&stylesheet.0, stylesheet.read() + "\n.mine { color: red; }\n").unwrap();
+        let before = stylesheet.read();
+
+        assert!(stylesheet.merge("neon-timeline", &timeline_css("#FF0066")));
+        assert_eq!(stylesheet.read(), before.replace("--timeline-line: #4C43CD;", "--timeline-line: #FF0066;"));
+    }
+
+    #[test]
+    fn strip_restores_the_stylesheet() {
+        for original in [GLOBALS, "", "body {}\n\n\n", "/* nothing else */\n"] {
+            let stylesheet = TempStylesheet::new("strip", original);
+            stylesheet.merge("neon-timeline", &timeline_css("#4C43CD"));
+            assert!(stylesheet.strip("neon-timeline"));
+            assert_eq!(stylesheet.read(), original);
+        }
+    }
+
+    #[test]
+    fn strip_leaves_other_components() {
+        let only_underline = TempStylesheet::new("strip-other-expected", GLOBALS);
+        only_underline.merge("neon-underline", &underline_css());
+
+        let stylesheet = TempStylesheet::new("strip-other", GLOBALS);
+        stylesheet.merge("neon-timeline", &timeline_css("#4C43CD"));
+        stylesheet.merge("neon-underline", &underline_css());
+        assert!(stylesheet.strip("neon-timeline"));
+        assert_eq!(stylesheet.read(), only_underline.read());
+
+        assert!(stylesheet.strip("neon-underline"));
+        assert_eq!(stylesheet.read(), GLOBALS);
+    }
+
+    #[test]
+    fn strip_without_a_block_changes_nothing() {
+        let stylesheet = TempStylesheet::new("strip-none", GLOBALS);
+        assert!(!stylesheet.strip("neon-timeline"));
+        assert_eq!(stylesheet.read(), GLOBALS);
+    }
+}
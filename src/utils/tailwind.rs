@@ -0,0 +1,227 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::Result;
+use glob::{MatchOptions, Pattern};
+
+use crate::models::component::{FileType, TailwindRequirement};
+use crate::models::project::{ProjectInfo, TailwindInfo};
+use crate::utils::project::{get_installed_version, get_package_version, parse_major_version};
+use crate::utils::tailwind_config::{add_content_glob, class_prefix, content_globs};
+use crate::utils::ui::{confirm, print_success, print_warning};
+
+const CONFIG_FILES: &[&str] = &[
+    "tailwind.config.ts",
+    "tailwind.config.js",
+    "tailwind.config.mjs",
+    "tailwind.config.cjs",
+];
+
+// Where Next.js projects usually keep their global stylesheet
+const CSS_FILES: &[&str] = &[
+    "app/globals.css",
+    "src/app/globals.css",
+    "styles/globals.css",
+    "src/styles/globals.css",
+    "app/global.css",
+    "src/app/global.css",
+];
+
+// Target dirs vin-ui installs scripts into, whose classes Tailwind has to scan
+pub const SCRIPT_FILE_TYPES: &[FileType] = &[FileType::Component, FileType::Hook, FileType::Lib, FileType::Page, FileType::ApiRoute];
+
+pub fn find_tailwind_config(project_root: &Path) -> Option<PathBuf> {
+    CONFIG_FILES.iter()
+        .map(|file| project_root.join(file))
+        .find(|path| path.is_file())
+}
+
+// v4 is loaded with `@import "tailwindcss"`, v3 with `@tailwind` directives
+fn css_tailwind_version(content: &str) -> Option<u32> {
+    if content.contains("@import \"tailwindcss\"") || content.contains("@import 'tailwindcss'") {
+        Some(4)
+    } else if content.contains("@tailwind ") {
+        Some(3)
+    } else {
+        None
+    }
+}
+
+// v4 sets a prefix on the import, `@import "tailwindcss" prefix(tw);`, used as `tw:flex`
+fn css_prefix(content: &str) -> Option<String> {
+    let line = content.lines()
+        .find(|line| line.contains("@import") && line.contains("tailwindcss"))?;
+    let start = line.find("prefix(")? + "prefix(".len();
+    let end = start + line[start..].find(')')?;
+    let prefix = line[start..end].trim();
+    (!prefix.is_empty()).then(|| format!("{}:", prefix))
+}
+
+// The project's global stylesheet: the first one loading Tailwind, or else the first that exists
+pub fn find_global_css(project_root: &Path) -> Option<PathBuf> {
+    let existing: Vec<PathBuf> = CSS_FILES.iter()
+        .map(|file| project_root.join(file))
+        .filter(|path| path.is_file())
+        .collect();
+
+    existing.iter()
+        .find(|path| fs::read_to_string(path).ok().and_then(|content| css_tailwind_version(&content)).is_some())
+        .or(existing.first())
+        .cloned()
+}
+
+// Tailwind is in use when it is a dependency, has a config file, or the
+// global stylesheet loads it. The major version comes from, in order: the
+// installed package, package.json, the v4-only @tailwindcss/postcss plugin,
+// and the way the stylesheet loads Tailwind.
+pub fn detect_tailwind(project_root: &Path) -> Option<TailwindInfo> {
+    let package_version = get_package_version(project_root, "tailwindcss");
+    let config_file = find_tailwind_config(project_root);
+    let css_file = find_global_css(project_root);
+    let css_content = css_file.as_ref().and_then(|path| fs::read_to_string(path).ok());
+    let css_version = css_content.as_deref().and_then(css_tailwind_version);
+
+    if package_version.is_none() && config_file.is_none() && css_version.is_none() {
+        return None;
+    }
+
+    // the installed version is more precise than the range in package.json
+    let version = get_installed_version(project_root, "tailwindcss").as_deref().and_then(parse_major_version)
+        .or_else(|| package_version.as_deref().and_then(parse_major_version))
+        .or_else(|| get_package_version(project_root, "@tailwindcss/postcss").map(|_| 4))
+        .or(css_version);
+
+    let prefix = match version {
+        Some(4) => css_content.as_deref().and_then(css_prefix),
+        _ => config_file.as_ref()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|src| class_prefix(&src)),
+    };
+
+    Some(TailwindInfo { version, config_file, css_file, prefix })
+}
+
+
+pub enum TailwindCheck {
+    Compatible,
+    Variant(u32, String),       // The entry file written for the project's version
+    NotDetected,
+    UnknownVersion,
+    Mismatch(u32),              // The project's version, with no variant for it
+}
+
+pub fn check_tailwind(requirement: &TailwindRequirement, project: &ProjectInfo) -> TailwindCheck {
+    let Some(tailwind) = &project.tailwind else {
+        return TailwindCheck::NotDetected;
+    };
+
+    match tailwind.version {
+        None => TailwindCheck::UnknownVersion,
+        Some(version) if version == requirement.version => TailwindCheck::Compatible,
+        Some(version) => match requirement.variants.as_ref().and_then(|variants| variants.get(&version)) {
+            Some(file) => TailwindCheck::Variant(version, file.clone()),
+            None => TailwindCheck::Mismatch(version),
+        },
+    }
+}
+
+pub enum ContentCoverage {
+    Covered,
+    Missing(String),        // Glob that would cover the directory
+    Unknown(String),        // Why it couldn't be checked
+    NotApplicable,          // No Tailwind, or v4 without a config detecting sources itself
+}
+
+// Expand {a,b} alternatives, which glob patterns don't support
+fn expand_braces(pattern: &str) -> Vec<String> {
+    let Some(open) = pattern.find('{') else {
+        return vec![pattern.to_string()];
+    };
+    let Some(close) = pattern[open..].find('}').map(|offset| open + offset) else {
+        return vec![pattern.to_string()];
+    };
+
+    pattern[open + 1..close]
+        .split(',')
+        .flat_map(|alternative| expand_braces(&format!("{}{}{}", &pattern[..open], alternative, &pattern[close + 1..])))
+        .collect()
+}
+
+fn relative_dir(project_root: &Path, dir: &Path) -> String {
+    dir.strip_prefix(project_root)
+        .unwrap_or(dir)
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+// Whether the globs match both single-file and directory components installed in dir
+fn globs_cover(globs: &[String], relative_dir: &str) -> bool {
+    let samples = [
+        format!("{}/component.tsx", relative_dir),
+        format!("{}/component/component.tsx", relative_dir),
+    ];
+    let options = MatchOptions { require_literal_separator: true, ..MatchOptions::new() };
+
+    samples.iter().all(|sample| {
+        globs.iter()
+            .filter(|glob| !glob.starts_with('!'))
+            .flat_map(|glob| expand_braces(glob))
+            .filter_map(|glob| Pattern::new(glob.trim_start_matches("./")).ok())
+            .any(|pattern| pattern.matches_with(sample, options))
+    })
+}
+
+// Whether Tailwind's `content` globs include dir, so classes used there aren't purged
+pub fn check_content_coverage(project_root: &Path, project: &ProjectInfo, dir: &Path) -> ContentCoverage {
+    let Some(config_file) = project.tailwind.as_ref().and_then(|tailwind| tailwind.config_file.as_ref()) else {
+        return ContentCoverage::NotApplicable;
+    };
+
+    let Ok(src) = fs::read_to_string(config_file) else {
+        return ContentCoverage::Unknown(format!("couldn't read {}", config_file.display()));
+    };
+    let Some(globs) = content_globs(&src) else {
+        // v4 finds sources on its own when a config doesn't list them
+        if project.tailwind_version() == Some(4) {
+            return ContentCoverage::NotApplicable;
+        }
+        return ContentCoverage::Unknown(format!("couldn't read the content globs in {}", config_file.display()));
+    };
+
+    let relative_dir = relative_dir(project_root, dir);
+    if globs_cover(&globs, &relative_dir) {
+        ContentCoverage::Covered
+    } else {
+        ContentCoverage::Missing(format!("./{}/**/*.{{js,ts,jsx,tsx,mdx}}", relative_dir))
+    }
+}
+
+// Warn when dir isn't covered by the content globs, and offer to add a glob for it
+pub fn offer_content_glob(project_root: &Path, project: &ProjectInfo, dir: &Path) -> Result<()> {
+    let Some(config_file) = project.tailwind.as_ref().and_then(|tailwind| tailwind.config_file.as_ref()) else {
+        return Ok(());
+    };
+
+    match check_content_coverage(project_root, project, dir) {
+        ContentCoverage::Missing(glob) => {
+            print_warning(&format!(
+                "The content globs in {} don't include {}, Tailwind would purge the classes used there",
+                config_file.display(), dir.display()
+            ));
+
+            if confirm(&format!("Add \"{}\" to content?", glob), true) {
+                match add_content_glob(config_file, &glob) {
+                    Ok(()) => print_success(&format!("Added \"{}\" to {}", glob, config_file.display())),
+                    Err(err) => print_warning(&format!("{:#}, add \"{}\" to content by hand", err, glob)),
+                }
+            }
+        }
+        ContentCoverage::Unknown(reason) => print_warning(&format!(
+            "Couldn't check that Tailwind scans {}: {}",
+            dir.display(), reason
+        )),
+        ContentCoverage::Covered | ContentCoverage::NotApplicable => {}
+    }
+    Ok(())
+}
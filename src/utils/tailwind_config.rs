@@ -0,0 +1,616 @@
+use std::fs;
+use std::path::Path;
+use anyhow::{Result, Context, bail};
+use serde_json::{Map, Value};
+
+// Just enough of a JS/TS scanner to find and edit object literals in a
+// tailwind.config file: strings, template literals and comments are skipped,
+// anything that isn't a plain object literal where we expect one is refused.
+
+fn skip_string(src: &[u8], start: usize) -> usize {
+    let quote = src[start];
+    let mut i = start + 1;
+    while i < src.len() {
+        match src[i] {
+            b'\\' => i += 2,
+            b'$' if quote == b'`' && src.get(i + 1) == Some(&b'{') => i = skip_interpolation(src, i + 2),
+            c if c == quote => return i + 1,
+            _ => i += 1,
+        }
+    }
+    src.len()
+}
+
+// Past the `}` closing a template literal's `${`, whose expression starts at i
+fn skip_interpolation(src: &[u8], mut i: usize) -> usize {
+    let mut depth = 0usize;
+    while i < src.len() {
+        i = skip_trivia(src, i);
+        match src.get(i) {
+            None => break,
+            Some(b'"' | b'\'' | b'`') => {
+                i = skip_string(src, i);
+                continue;
+            }
+            Some(b'{') => depth += 1,
+            Some(b'}') if depth == 0 => return i + 1,
+            Some(b'}') => depth -= 1,
+            _ => {}
+        }
+        i += 1;
+    }
+    src.len()
+}
+
+// Index of the next character that isn't whitespace or part of a comment
+fn skip_trivia(src: &[u8], mut i: usize) -> usize {
+    while i < src.len() {
+        if src[i].is_ascii_whitespace() {
+            i += 1;
+        } else if src[i..].starts_with(b"//") {
+            while i < src.len() && src[i] != b'\n' {
+                i += 1;
+            }
+        } else if src[i..].starts_with(b"/*") {
+            i = src[i + 2..].windows(2)
+                .position(|w| w == b"*/")
+                .map(|p| i + 2 + p + 2)
+                .unwrap_or(src.len());
+        } else {
+            break;
+        }
+    }
+    i
+}
+
+// End of the expression starting at i: the next `,` or closing bracket at depth 0
+fn expression_end(src: &[u8], mut i: usize) -> usize {
+    let mut depth = 0usize;
+    while i < src.len() {
+        i = skip_trivia(src, i);
+        if i >= src.len() {
+            break;
+        }
+        match src[i] {
+            b'"' | b'\'' | b'`' => {
+                i = skip_string(src, i);
+                continue;
+            }
+            b'{' | b'[' | b'(' => depth += 1,
+            b'}' | b']' | b')' if depth == 0 => return i,
+            b'}' | b']' | b')' => depth -= 1,
+            b',' if depth == 0 => return i,
+            _ => {}
+        }
+        i += 1;
+    }
+    src.len()
+}
+
+pub struct Entry {
+    pub key: String,
+    pub value_start: usize,
+    pub value_end: usize,       // Exclusive, trailing whitespace trimmed
+}
+
+// Entries of the object literal whose `{` is at open, and the index of its `}`
+pub fn object_entries(src: &str, open: usize) -> Option<(Vec<Entry>, usize)> {
+    let bytes = src.as_bytes();
+    if bytes.get(open) != Some(&b'{') {
+        return None;
+    }
+
+    let mut entries = Vec::new();
+    let mut i = open + 1;
+    loop {
+        i = skip_trivia(bytes, i);
+        match bytes.get(i)? {
+            b'}' => return Some((entries, i)),
+            b',' => {
+                i += 1;
+                continue;
+            }
+            _ => {}
+        }
+
+        // key: identifier or quoted string, anything else (spreads, computed keys) is skipped
+        let key = match bytes[i] {
+            b'"' | b'\'' => {
+                let end = skip_string(bytes, i);
+                let key = src[i + 1..end - 1].to_string();
+                i = end;
+                Some(key)
+            }
+            c if c.is_ascii_alphanumeric() || c == b'_' || c == b'$' => {
+                let start = i;
+                while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_' || bytes[i] == b'$') {
+                    i += 1;
+                }
+                Some(src[start..i].to_string())
+            }
+            _ => None,
+        };
+
+        let after_key = skip_trivia(bytes, i);
+        match (key, bytes.get(after_key)) {
+            (Some(key), Some(b':')) => {
+                let value_start = skip_trivia(bytes, after_key + 1);
+                let end = expression_end(bytes, value_start);
+                let value_end = value_start + src[value_start..end].trim_end().len();
+                entries.push(Entry { key, value_start, value_end });
+                i = end;
+            }
+            _ => i = expression_end(bytes, i),
+        }
+
+        if i >= bytes.len() {
+            return None;
+        }
+    }
+}
+
+fn is_word_byte(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || c == b'_' || c == b'$'
+}
+
+// Identifiers outside strings and comments, dotted ones like `module.exports`
+// as one, by their (start, end)
+fn words(src: &str) -> Vec<(usize, usize)> {
+    let bytes = src.as_bytes();
+    let mut words = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        i = skip_trivia(bytes, i);
+        if i >= bytes.len() {
+            break;
+        }
+        match bytes[i] {
+            b'"' | b'\'' | b'`' => i = skip_string(bytes, i),
+            c if is_word_byte(c) => {
+                let start = i;
+                while i < bytes.len() && (is_word_byte(bytes[i]) || bytes[i] == b'.') {
+                    i += 1;
+                }
+                words.push((start, i));
+            }
+            _ => i += 1,
+        }
+    }
+    words
+}
+
+// The object literal an expression stands for: the literal itself, the first
+// argument of a wrapper like defineConfig({ ... }), or what a variable is set to
+fn object_of(src: &str, words: &[(usize, usize)], at: usize, depth: usize) -> Option<usize> {
+    if depth > 4 {
+        return None;
+    }
+
+    let bytes = src.as_bytes();
+    let at = skip_trivia(bytes, at);
+    match *bytes.get(at)? {
+        b'{' => Some(at),
+        b'(' => object_of(src, words, at + 1, depth + 1),
+        c if is_word_byte(c) => {
+            let &(_, end) = words.iter().find(|(start, _)| *start == at)?;
+            let after = skip_trivia(bytes, end);
+            if bytes.get(after) == Some(&b'(') {
+                return object_of(src, words, after + 1, depth + 1);
+            }
+
+            // `const config: Config = { ... }`
+            let name = &src[at..end];
+            let declaration = words.windows(2).find(|pair| {
+                matches!(&src[pair[0].0..pair[0].1], "const" | "let" | "var") && &src[pair[1].0..pair[1].1] == name
+            })?;
+            let equals = declaration[1].1 + src[declaration[1].1..].find('=')?;
+            object_of(src, words, equals + 1, depth + 1)
+        }
+        _ => None,
+    }
+}
+
+// The exported config object, what `module.exports =` or `export default` is given
+fn find_root_object(src: &str) -> Option<usize> {
+    let bytes = src.as_bytes();
+    let words = words(src);
+    let word = |index: usize| words.get(index).map(|(start, end)| &src[*start..*end]);
+
+    for (index, &(_, end)) in words.iter().enumerate() {
+        let after = skip_trivia(bytes, end);
+        let exported = match word(index) {
+            Some("module.exports") if bytes.get(after) == Some(&b'=') && bytes.get(after + 1) != Some(&b'=') => after + 1,
+            Some("export") if word(index + 1) == Some("default") && words[index + 1].0 == after => words[index + 1].1,
+            _ => continue,
+        };
+        return object_of(src, &words, exported, 0);
+    }
+    None
+}
+
+// Follow a path of keys from the root object, to the value of the last one
+pub fn find_value<'a>(src: &'a str, path: &[&str]) -> Option<&'a str> {
+    let mut open = find_root_object(src)?;
+    let (last, parents) = path.split_last()?;
+
+    for key in parents {
+        let (entries, _) = object_entries(src, open)?;
+        let entry = entries.into_iter().find(|entry| entry.key == *key)?;
+        open = entry.value_start;
+    }
+
+    let (entries, _) = object_entries(src, open)?;
+    entries.into_iter()
+        .find(|entry| entry.key == *last)
+        .map(|entry| &src[entry.value_start..entry.value_end])
+}
+
+fn indent_unit(src: &str) -> String {
+    src.lines()
+        .filter(|line| !line.trim_start().starts_with('*'))
+        .map(|line| line.len() - line.trim_start().len())
+        .filter(|width| *width > 0)
+        .min()
+        .map(|width| if src.lines().any(|line| line.starts_with('\t')) { "\t".to_string() } else { " ".repeat(width) })
+        .unwrap_or_else(|| "  ".to_string())
+}
+
+fn line_indent(src: &str, at: usize) -> String {
+    let line_start = src[..at].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    src[line_start..].chars().take_while(|c| c.is_whitespace() && *c != '\n').collect()
+}
+
+fn js_key(key: &str) -> String {
+    let is_identifier = key.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    if is_identifier {
+        key.to_string()
+    } else {
+        serde_json::to_string(key).unwrap_or_default()
+    }
+}
+
+// A JSON value as a JS literal, nested lines indented below `indent`
+fn js_value(value: &Value, indent: &str, unit: &str) -> String {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            let inner = format!("{}{}", indent, unit);
+            let entries: Vec<String> = map.iter()
+                .map(|(key, value)| format!("{}{}: {},", inner, js_key(key), js_value(value, &inner, unit)))
+                .collect();
+            format!("{{\n{}\n{}}}", entries.join("\n"), indent)
+        }
+        _ => value.to_string(),
+    }
+}
+
+// Add an item (rendered for a given indent and indent unit) as the last one of
+// the object or array closing at close, keeping the surrounding layout
+fn insert_item(src: &str, close: usize, render: impl FnOnce(&str, &str) -> String) -> String {
+    let unit = indent_unit(src);
+    let closing_indent = line_indent(src, close);
+    let closing_on_own_line = src[..close].trim_end_matches([' ', '\t']).ends_with('\n');
+
+    // the previous item needs a comma unless it already has one or the literal is empty
+    let before = src[..close].trim_end();
+    let is_empty = before.ends_with('{') || before.ends_with('[');
+    let needs_comma = !before.ends_with(',') && !is_empty;
+
+    let mut output = before.to_string();
+    if needs_comma {
+        output.push(',');
+    }
+
+    if closing_on_own_line {
+        let item_indent = format!("{}{}", closing_indent, unit);
+        output.push_str(&format!("\n{}{},\n{}", item_indent, render(&item_indent, &unit), closing_indent));
+    } else {
+        // single line literals stay on one line
+        output.push_str(if is_empty { "" } else { " " });
+        output.push_str(&render(&closing_indent, &unit));
+    }
+    output.push_str(&src[close..]);
+    output
+}
+
+fn insert_entry(src: &str, close: usize, key: &str, value: &Value) -> String {
+    insert_item(src, close, |indent, unit| format!("{}: {}", js_key(key), js_value(value, indent, unit)))
+}
+
+// Offset of a slice returned by find_value in the source
+fn offset_in(src: &str, value: &str) -> usize {
+    value.as_ptr() as usize - src.as_ptr() as usize
+}
+
+// The `]` closing the array literal opening at open
+fn array_close(src: &str, open: usize) -> Option<usize> {
+    let bytes = src.as_bytes();
+    let mut i = open + 1;
+    loop {
+        i = skip_trivia(bytes, i);
+        match bytes.get(i)? {
+            b']' => return Some(i),
+            b',' => i += 1,
+            _ => i = expression_end(bytes, i),
+        }
+    }
+}
+
+// String items of the array literal value, None when it isn't one. Computed
+// items (spreads, calls, ...) are skipped.
+fn string_items(value: &str) -> Option<Vec<String>> {
+    if !value.starts_with('[') {
+        return None;
+    }
+
+    let bytes = value.as_bytes();
+    let close = array_close(value, 0)?;
+    let mut items = Vec::new();
+    let mut i = 1;
+    loop {
+        i = skip_trivia(bytes, i);
+        if i >= close {
+            break;
+        }
+        match bytes[i] {
+            b',' => i += 1,
+            b'"' | b'\'' | b'`' => {
+                let end = skip_string(bytes, i);
+                items.push(value[i + 1..end - 1].to_string());
+                i = end;
+            }
+            _ => i = expression_end(bytes, i),
+        }
+    }
+    Some(items)
+}
+
+// The `content` globs of a tailwind.config, from `content: [...]` or `content: { files: [...] }`
+pub fn content_globs(src: &str) -> Option<Vec<String>> {
+    find_value(src, &["content"])
+        .and_then(string_items)
+        .or_else(|| find_value(src, &["content", "files"]).and_then(string_items))
+}
+
+// The class `prefix` a v3 config sets, e.g. `tw-`
+pub fn class_prefix(src: &str) -> Option<String> {
+    let value = find_value(src, &["prefix"])?.trim();
+    let quote = value.chars().next().filter(|c| matches!(c, '"' | '\'' | '`'))?;
+    let prefix = value.strip_prefix(quote)?.strip_suffix(quote)?;
+    (!prefix.is_empty()).then(|| prefix.to_string())
+}
+
+pub fn add_content_glob(config_file: &Path, glob: &str) -> Result<()> {
+    let src = fs::read_to_string(config_file)
+        .context(format!("Failed to read {}", config_file.display()))?;
+
+    let array = find_value(&src, &["content"])
+        .filter(|value| value.starts_with('['))
+        .or_else(|| find_value(&src, &["content", "files"]).filter(|value| value.starts_with('[')))
+        .context(format!("Can't safely edit {}: `content` is not an array literal", config_file.display()))?;
+    let open = offset_in(&src, array);
+    let close = array_close(&src, open)
+        .context(format!("Couldn't parse {}", config_file.display()))?;
+
+    let glob = serde_json::to_string(glob)?;
+    let src = insert_item(&src, close, |_, _| glob);
+    fs::write(config_file, src)
+        .context(format!("Failed to write {}", config_file.display()))?;
+    Ok(())
+}
+
+// The `{` of the object literal at path, refusing values that aren't object literals
+fn object_at(src: &str, path: &[&str], file: &Path) -> Result<Option<usize>> {
+    let mut open = find_root_object(src)
+        .context(format!("Couldn't find the exported config object in {}", file.display()))?;
+
+    for (depth, key) in path.iter().enumerate() {
+        let (entries, _) = object_entries(src, open)
+            .context(format!("Couldn't parse {}", file.display()))?;
+        let Some(entry) = entries.into_iter().find(|entry| entry.key == *key) else {
+            return Ok(None);
+        };
+
+        if !src[entry.value_start..].starts_with('{') {
+            bail!(
+                "Can't safely edit {}: `{}` is not an object literal",
+                file.display(),
+                path[..=depth].join(".")
+            );
+        }
+        open = entry.value_start;
+    }
+    Ok(Some(open))
+}
+
+fn object(key: &str, value: Value) -> Value {
+    Value::Object(Map::from_iter([(key.to_string(), value)]))
+}
+
+// Insert theme extensions into `theme.extend` of a tailwind.config file. Entries
+// the file already defines are left alone. Returns a line per change or skip.
+pub fn extend_theme(config_file: &Path, extend: &Map<String, Value>) -> Result<Vec<String>> {
+    let original = fs::read_to_string(config_file)
+        .context(format!("Failed to read {}", config_file.display()))?;
+    let mut src = original.clone();
+    let mut report = Vec::new();
+
+    for (section, entries) in extend {
+        let Value::Object(entries) = entries else {
+            bail!("theme extension '{}' must be an object", section);
+        };
+
+        for (name, value) in entries {
+            let label = format!("theme.extend.{}.{}", section, name);
+            let section_value = object(name, value.clone());
+
+            // insert at the deepest level that exists, creating the levels below it
+            let levels: [(&[&str], &str, Value); 4] = [
+                (&["theme", "extend", section], name, value.clone()),
+                (&["theme", "extend"], section, section_value.clone()),
+                (&["theme"], "extend", object(section, section_value.clone())),
+                (&[], "theme", object("extend", object(section, section_value))),
+            ];
+
+            for (path, key, insert) in levels {
+                let Some(open) = object_at(&src, path, config_file)? else {
+                    continue;
+                };
+                let (existing, close) = object_entries(&src, open)
+                    .context(format!("Couldn't parse {}", config_file.display()))?;
+
+                if existing.iter().any(|entry| entry.key == key) {
+                    report.push(format!("{} is already set, left unchanged", label));
+                } else {
+                    src = insert_entry(&src, close, key, &insert);
+                    report.push(format!("added {}", label));
+                }
+                break;
+            }
+        }
+    }
+
+    // never write something we can't read back
+    if find_value(&src, &["theme", "extend"]).is_none() && !extend.is_empty() {
+        bail!("Can't safely edit {}: the result could not be parsed", config_file.display());
+    }
+
+    if src != original {
+        fs::write(config_file, src)
+            .context(format!("Failed to write {}", config_file.display()))?;
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::path::PathBuf;
+
+    // A config file in the temp dir, removed when dropped
+    struct TempConfig(PathBuf);
+
+    impl TempConfig {
+        fn new(name: &str, src: &str) -> Self {
+            let path = std::env::temp_dir().join(format!("vin-ui-{}-{}.js", std::process::id(), name));
+            fs::write(&path, src).unwrap();
+            TempConfig(path)
+        }
+
+        fn read(&self) -> String {
+            fs::read_to_string(&self.0).unwrap()
+        }
+    }
+
+    impl Drop for TempConfig {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.0);
+        }
+    }
+
+    fn extend(value: Value) -> Map<String, Value> {
+        value.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn reads_module_exports() {
+        let src = "/** @type {import('tailwindcss').Config} */\nmodule.exports = {\n  content: ['./app/**/*.tsx', \"./components/**/*.tsx\"],\n  prefix: 'tw-',\n}\n";
+        assert_eq!(content_globs(src).unwrap(), ["./app/**/*.tsx", "./components/**/*.tsx"]);
+        assert_eq!(class_prefix(src).as_deref(), Some("tw-"));
+    }
+
+    #[test]
+    fn reads_export_default() {
+        let src = "export default {\n  content: { files: ['./src/**/*.tsx'] },\n}\n";
+        assert_eq!(content_globs(src).unwrap(), ["./src/**/*.tsx"]);
+    }
+
+    #[test]
+    fn reads_typed_and_satisfies_configs() {
+        let typed = "import type { Config } from 'tailwindcss'\n\nconst config: Config = {\n  content: ['./app/**/*.tsx'],\n}\nexport default config\n";
+        assert_eq!(content_globs(typed).unwrap(), ["./app/**/*.tsx"]);
+
+        let satisfies = "export default {\n  content: ['./app/**/*.tsx'],\n} satisfies Config\n";
+        assert_eq!(content_globs(satisfies).unwrap(), ["./app/**/*.tsx"]);
+
+        let variable = "const config = {\n  content: ['./app/**/*.tsx'],\n} satisfies Config\n\nexport default config\n";
+        assert_eq!(content_globs(variable).unwrap(), ["./app/**/*.tsx"]);
+    }
+
+    #[test]
+    fn follows_wrappers_but_not_other_calls() {
+        let wrapped = "module.exports = withMT({ content: ['./app/**/*.tsx'] })\n";
+        assert_eq!(content_globs(wrapped).unwrap(), ["./app/**/*.tsx"]);
+
+        // a preset built before the export isn't the config
+        let preset = "const preset = createPreset({ content: ['./wrong/**'], theme: {} })\nmodule.exports = { presets: [preset], content: ['./app/**'] }\n";
+        assert_eq!(content_globs(preset).unwrap(), ["./app/**"]);
+    }
+
+    #[test]
+    fn skips_template_literal_interpolations() {
+        let src = "const dir = `${flag ? `it's` : `app`}/${['}'][0]}`\nmodule.exports = {\n  content: [`${dir}/**/*.tsx`, './app/**'],\n  prefix: `tw-`,\n}\n";
+        assert_eq!(content_globs(src).unwrap(), ["${dir}/**/*.tsx", "./app/**"]);
+        assert_eq!(class_prefix(src).as_deref(), Some("tw-"));
+    }
+
+    #[test]
+    fn adds_content_globs() {
+        let config = TempConfig::new("content", "module.exports = {\n  content: [\n    './app/**/*.tsx',\n  ],\n}\n");
+        add_content_glob(&config.0, "./components/ui/**/*.tsx").unwrap();
+        assert_eq!(config.read(), "module.exports = {\n  content: [\n    './app/**/*.tsx',\n    \"./components/ui/**/*.tsx\",\n  ],\n}\n");
+
+        let inline = TempConfig::new("content-inline", "export default { content: ['./app/**'] }\n");
+        add_content_glob(&inline.0, "./ui/**").unwrap();
+        assert_eq!(inline.read(), "export default { content: ['./app/**', \"./ui/**\"] }\n");
+
+        let computed = TempConfig::new("content-computed", "module.exports = { content: require('./globs') }\n");
+        assert!(add_content_glob(&computed.0, "./ui/**").is_err());
+    }
+
+    #[test]
+    fn extends_existing_extend_section() {
+        let config = TempConfig::new("extend", "module.exports = {\n  content: [],\n  theme: {\n    extend: {\n      colors: { brand: '#fff' },\n    },\n  },\n}\n");
+        let report = extend_theme(&config.0, &extend(json!({ "animation": { "glow": "glow 2s infinite" } }))).unwrap();
+
+        assert_eq!(report, ["added theme.extend.animation.glow"]);
+        assert_eq!(
+            find_value(&config.read(), &["theme", "extend", "animation", "glow"]),
+            Some("\"glow 2s infinite\"")
+        );
+        assert_eq!(find_value(&config.read(), &["theme", "extend", "colors", "brand"]), Some("'#fff'"));
+    }
+
+    #[test]
+    fn creates_missing_sections() {
+        let config = TempConfig::new("create", "const config: Config = {\n  content: [],\n}\nexport default config\n");
+        extend_theme(&config.0, &extend(json!({ "keyframes": { "glow": { "50%": { "opacity": "0.5" } } } }))).unwrap();
+
+        assert_eq!(
+            find_value(&config.read(), &["theme", "extend", "keyframes", "glow", "50%", "opacity"]),
+            Some("\"0.5\"")
+        );
+    }
+
+    #[test]
+    fn leaves_keys_already_set() {
+        let src = "export default {\n  theme: {\n    extend: {\n      animation: { glow: 'mine' },\n    },\n  },\n} satisfies Config\n";
+        let config = TempConfig::new("already-set", src);
+        let report = extend_theme(&config.0, &extend(json!({ "animation": { "glow": "glow 2s infinite" } }))).unwrap();
+
+        assert_eq!(report, ["theme.extend.animation.glow is already set, left unchanged"]);
+        assert_eq!(config.read(), src);
+    }
+
+    #[test]
+    fn refuses_values_that_are_not_object_literals() {
+        let src = "module.exports = {\n  theme: require('./theme'),\n}\n";
+        let config = TempConfig::new("not-object", src);
+        assert!(extend_theme(&config.0, &extend(json!({ "animation": { "glow": "x" } }))).is_err());
+        assert_eq!(config.read(), src);
+
+        let shared = TempConfig::new("not-object-extend", "module.exports = { theme: { extend: sharedExtend } }\n");
+        assert!(extend_theme(&shared.0, &extend(json!({ "animation": { "glow": "x" } }))).is_err());
+    }
+}
@@ -1,53 +1,93 @@
-use std::path::{Path, PathBuf};
+use std::path::PathBuf;
 use anyhow::{Result, Context};
-use std::fs;
 
-pub fn get_templates_dir() -> PathBuf {
-    // Try several locations in order of preference:
-    
+use crate::utils::builtin::builtin_templates_dir;
+use crate::utils::component::is_directory_component;
+use crate::utils::block::is_block;
+use crate::utils::hook::is_hook;
+use crate::utils::theme::is_theme;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LocationKind {
+    EnvVar,
+    BinaryAdjacent,
+    ConfigDir,
+    Ancestor,
+    Builtin,
+}
+
+impl LocationKind {
+    pub fn describe(&self) -> &'static str {
+        match self {
+            LocationKind::EnvVar => "VIN_UI_TEMPLATES environment variable",
+            LocationKind::BinaryAdjacent => "templates/ next to the vin-ui binary",
+            LocationKind::ConfigDir => "user config directory",
+            LocationKind::Ancestor => "templates/ in the current directory or a parent",
+            LocationKind::Builtin => "templates built into vin-ui",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TemplateLocation {
+    pub kind: LocationKind,
+    pub path: PathBuf,
+}
+
+// Every place templates are looked for, in order of preference, whether it exists or not
+pub fn get_candidate_locations() -> Vec<TemplateLocation> {
+    let mut candidates = Vec::new();
+
     // 1. Check environment variable (useful for development & testing)
     if let Ok(template_path) = std::env::var("VIN_UI_TEMPLATES") {
-        let path = PathBuf::from(template_path);
-        if path.exists() && path.is_dir() {
-            return path;
-        }
+        candidates.push(TemplateLocation { kind: LocationKind::EnvVar, path: PathBuf::from(template_path) });
     }
-    
+
     // 2. Check for templates next to the binary
     if let Ok(exe_path) = std::env::current_exe() {
-        let binary_adjacent = exe_path.parent().unwrap().join("templates");
-        if binary_adjacent.exists() && binary_adjacent.is_dir() {
-            return binary_adjacent;
+        if let Some(exe_dir) = exe_path.parent() {
+            candidates.push(TemplateLocation { kind: LocationKind::BinaryAdjacent, path: exe_dir.join("templates") });
         }
     }
-    
+
     // 3. Check user's config directory
-    if let Some(config_dir) = dirs::config_dir() {
-        let config_templates = config_dir.join("vin-ui").join("templates");
-        if config_templates.exists() && config_templates.is_dir() {
-            return config_templates;
-        }
+    if let Ok(config_templates) = get_config_templates_dir() {
+        candidates.push(TemplateLocation { kind: LocationKind::ConfigDir, path: config_templates });
     }
-    
+
     // 4. Check current directory and parents (for development)
-    if let Ok(current_dir) = std::env::current_dir() {
-        let mut dir = current_dir;
-        loop {
-            let templates_dir = dir.join("templates");
-            if templates_dir.exists() && templates_dir.is_dir() {
-                return templates_dir;
-            }
-            
-            // Move up one directory
-            if let Some(parent) = dir.parent() {
-                dir = parent.to_path_buf();
-            } else {
-                break;
-            }
-        }
+    if let Some(dev_templates) = find_dev_templates() {
+        candidates.push(TemplateLocation { kind: LocationKind::Ancestor, path: dev_templates });
     }
-    
-    // 5. If couldn't find templates, return a default location
+
+    // 5. Fall back to the templates bundled into the binary
+    if let Ok(builtin_dir) = builtin_templates_dir() {
+        candidates.push(TemplateLocation { kind: LocationKind::Builtin, path: builtin_dir });
+    }
+
+    candidates
+}
+
+// Every existing templates directory, in order of preference
+pub fn get_template_locations() -> Vec<TemplateLocation> {
+    get_candidate_locations()
+        .into_iter()
+        .filter(|location| location.path.is_dir())
+        .collect()
+}
+
+pub fn get_config_templates_dir() -> Result<PathBuf> {
+    let config_dir = dirs::config_dir()
+        .context("Could not find user config directory")?;
+    Ok(config_dir.join("vin-ui").join("templates"))
+}
+
+pub fn get_templates_dir() -> PathBuf {
+    if let Some(location) = get_template_locations().into_iter().next() {
+        return location.path;
+    }
+
+    // Nothing found, not even the built-in templates.
     // This will typically cause a "templates not found" error later
     if let Some(config_dir) = dirs::config_dir() {
         config_dir.join("vin-ui").join("templates")
@@ -56,45 +96,57 @@ pub fn get_templates_dir() -> PathBuf {
     }
 }
 
-// Install templates to the user's config directory
-pub fn install_templates() -> Result<PathBuf> {
-    // First try to find development templates
-    let dev_templates = find_dev_templates();
-    
-    // Get the user's config directory
-    let config_dir = dirs::config_dir()
-        .context("Could not find user config directory")?
-        .join("vin-ui");
-    
-    let templates_dest = config_dir.join("templates");
-    
-    // Only install if we found development templates and the destination doesn't exist
-    if let Some(source_dir) = dev_templates {
-        if !templates_dest.exists() {
-            // Create the destination directory
-            fs::create_dir_all(&templates_dest)
-                .context("Failed to create templates directory")?;
-            
-            // Copy all templates
-            copy_dir_recursively(&source_dir, &templates_dest)
-                .context("Failed to copy templates")?;
-            
-            println!("Installed templates to {}", templates_dest.display());
-        }
-    }
-    
-    Ok(templates_dest)
+// The templates directory providing a component: the first location that has
+// it, so user directories override the built-in templates one component at a time
+pub fn find_component_dir(component_name: &str) -> Option<PathBuf> {
+    get_template_locations()
+        .into_iter()
+        .map(|location| location.path)
+        .find(|path| {
+            path.join(format!("{}.tsx", component_name)).exists()
+                || is_directory_component(path, component_name)
+        })
+}
+
+// The templates directory providing a hook, resolved like components
+pub fn find_hook_dir(hook_name: &str) -> Option<PathBuf> {
+    get_template_locations()
+        .into_iter()
+        .map(|location| location.path)
+        .find(|path| is_hook(path, hook_name))
+}
+
+// The templates directory providing a block, resolved like components
+pub fn find_block_dir(block_name: &str) -> Option<PathBuf> {
+    get_template_locations()
+        .into_iter()
+        .map(|location| location.path)
+        .find(|path| is_block(path, block_name))
+}
+
+// The templates directory providing a theme, resolved like components
+pub fn find_theme_dir(theme_name: &str) -> Option<PathBuf> {
+    get_template_locations()
+        .into_iter()
+        .map(|location| location.path)
+        .find(|path| is_theme(path, theme_name))
 }
 
 // Find templates in development environment
-fn find_dev_templates() -> Option<PathBuf> {
-    // Try current directory and parents
+pub fn find_dev_templates() -> Option<PathBuf> {
+    find_ancestor_templates().into_iter().next()
+}
+
+// Every templates/ directory in the current directory and its parents, nearest first
+pub fn find_ancestor_templates() -> Vec<PathBuf> {
+    let mut found = Vec::new();
+
     if let Ok(current_dir) = std::env::current_dir() {
         let mut dir = current_dir;
         loop {
             let templates_dir = dir.join("templates");
             if templates_dir.exists() && templates_dir.is_dir() {
-                return Some(templates_dir);
+                found.push(templates_dir);
             }
             
             if let Some(parent) = dir.parent() {
@@ -105,30 +157,5 @@ fn find_dev_templates() -> Option<PathBuf> {
         }
     }
     
-    None
-}
-
-// Helper to copy directories recursively
-fn copy_dir_recursively(source: &Path, destination: &Path) -> Result<()> {
-    // Create the destination directory if it doesn't exist
-    if !destination.exists() {
-        fs::create_dir_all(destination)?;
-    }
-    
-    // Read source directory entries
-    for entry in fs::read_dir(source)? {
-        let entry = entry?;
-        let path = entry.path();
-        let dest_path = destination.join(path.file_name().unwrap());
-        
-        if path.is_dir() {
-            // Recursively copy subdirectories
-            copy_dir_recursively(&path, &dest_path)?;
-        } else {
-            // Copy files
-            fs::copy(&path, &dest_path)?;
-        }
-    }
-    
-    Ok(())
+    found
 }
@@ -0,0 +1,194 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::{Result, Context};
+
+use crate::models::store::StoreManifest;
+use crate::utils::builtin::builtin_templates_dir;
+use crate::utils::cache::sha256_hex;
+use crate::utils::component::list_template_files;
+use crate::utils::template_finder::{get_config_templates_dir, get_template_locations, LocationKind, TemplateLocation};
+
+const MANIFEST_FILE: &str = ".vin-ui-manifest.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileState {
+    UpToDate,
+    Outdated,   // Changed upstream, untouched locally
+    Modified,   // Changed locally since it was installed
+    Missing,    // In the source but not in the store
+    Extra,      // In the store but not in the source
+}
+
+impl FileState {
+    pub fn label(&self) -> &'static str {
+        match self {
+            FileState::UpToDate => "up to date",
+            FileState::Outdated => "outdated",
+            FileState::Modified => "modified",
+            FileState::Missing => "missing",
+            FileState::Extra => "extra",
+        }
+    }
+}
+
+pub struct UpdateReport {
+    pub updated: Vec<String>,
+    pub kept: Vec<(String, FileState)>,
+}
+
+// Where the config-dir copy is synced from: templates the user pointed vin-ui at
+// (VIN_UI_TEMPLATES or templates/ next to the binary), otherwise the templates
+// built into the binary. A templates/ folder that just happens to be in a parent
+// directory could be anything, so it's never a source.
+pub fn get_template_source() -> Result<TemplateLocation> {
+    let store = get_config_templates_dir()?;
+
+    let explicit = get_template_locations()
+        .into_iter()
+        .filter(|location| matches!(location.kind, LocationKind::EnvVar | LocationKind::BinaryAdjacent))
+        .find(|location| location.path != store);
+    if let Some(location) = explicit {
+        return Ok(location);
+    }
+
+    Ok(TemplateLocation { kind: LocationKind::Builtin, path: builtin_templates_dir()? })
+}
+
+// Template files in the store, without the store's own manifest
+fn list_files(root: &Path) -> Result<BTreeMap<String, PathBuf>> {
+    let mut files = list_template_files(root)?;
+    files.remove(MANIFEST_FILE);
+    Ok(files)
+}
+
+fn load_manifest(store: &Path) -> Result<StoreManifest> {
+    let path = store.join(MANIFEST_FILE);
+    if !path.exists() {
+        return Ok(StoreManifest::default());
+    }
+
+    let content = fs::read_to_string(&path)?;
+    serde_json::from_str(&content)
+        .context(format!("Failed to parse {}", path.display()))
+}
+
+fn save_manifest(store: &Path, manifest: &StoreManifest) -> Result<()> {
+    fs::write(store.join(MANIFEST_FILE), serde_json::to_string_pretty(manifest)?)?;
+    Ok(())
+}
+
+fn hash_file(path: &Path) -> Result<String> {
+    Ok(sha256_hex(&fs::read(path)?))
+}
+
+// Compare the store against its source, file by file
+pub fn store_status(store: &Path, source: &Path) -> Result<Vec<(String, FileState)>> {
+    let manifest = load_manifest(store)?;
+    let store_files = list_files(store)?;
+    let source_files = list_files(source)?;
+
+    let all: BTreeSet<&String> = store_files.keys().chain(source_files.keys()).collect();
+    let mut states = Vec::new();
+
+    for file in all {
+        let state = match (store_files.get(file), source_files.get(file)) {
+            (Some(local), Some(upstream)) => {
+                let local_hash = hash_file(local)?;
+                if local_hash == hash_file(upstream)? {
+                    FileState::UpToDate
+                } else if manifest.files.get(file) == Some(&local_hash) {
+                    FileState::Outdated
+                } else {
+                    FileState::Modified
+                }
+            }
+            (None, Some(_)) => FileState::Missing,
+            (Some(_), None) => FileState::Extra,
+            (None, None) => continue,
+        };
+        states.push((file.clone(), state));
+    }
+
+    Ok(states)
+}
+
+fn copy_file(source: &Path, dest: &Path) -> Result<String> {
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::copy(source, dest)
+        .context(format!("Failed to copy {} to {}", source.display(), dest.display()))?;
+    hash_file(dest)
+}
+
+// Install templates to the user's config directory, unless already there
+pub fn install_templates() -> Result<Option<PathBuf>> {
+    let store = get_config_templates_dir()?;
+    if store.exists() {
+        return Ok(None);
+    }
+
+    let source = get_template_source()?;
+    let mut manifest = StoreManifest {
+        source: source.path.display().to_string(),
+        files: BTreeMap::new(),
+    };
+
+    fs::create_dir_all(&store)
+        .context("Failed to create templates directory")?;
+
+    for (file, path) in list_files(&source.path)? {
+        let digest = copy_file(&path, &store.join(&file))?;
+        manifest.files.insert(file, digest);
+    }
+    save_manifest(&store, &manifest)?;
+
+    Ok(Some(store))
+}
+
+// Sync the store with its source. Files changed locally are kept unless forced.
+pub fn update_templates(force: bool) -> Result<UpdateReport> {
+    let store = get_config_templates_dir()?;
+    let source = get_template_source()?;
+    let source_files = list_files(&source.path)?;
+
+    let mut manifest = load_manifest(&store)?;
+    manifest.source = source.path.display().to_string();
+
+    let mut report = UpdateReport { updated: Vec::new(), kept: Vec::new() };
+
+    for (file, state) in store_status(&store, &source.path)? {
+        match state {
+            FileState::UpToDate => {
+                manifest.files.insert(file.clone(), hash_file(&store.join(&file))?);
+            }
+            FileState::Outdated | FileState::Missing => {
+                let digest = copy_file(&source_files[&file], &store.join(&file))?;
+                manifest.files.insert(file.clone(), digest);
+                report.updated.push(file);
+            }
+            FileState::Modified if force => {
+                let digest = copy_file(&source_files[&file], &store.join(&file))?;
+                manifest.files.insert(file.clone(), digest);
+                report.updated.push(file);
+            }
+            FileState::Modified | FileState::Extra => report.kept.push((file, state)),
+        }
+    }
+
+    save_manifest(&store, &manifest)?;
+    Ok(report)
+}
+
+// Throw away the store and install a fresh copy
+pub fn reset_templates() -> Result<PathBuf> {
+    let store = get_config_templates_dir()?;
+    if store.exists() {
+        fs::remove_dir_all(&store)
+            .context(format!("Failed to remove {}", store.display()))?;
+    }
+
+    install_templates()?;
+    Ok(store)
+}
@@ -0,0 +1,118 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use anyhow::{Result, Context, bail};
+
+use crate::models::component::ComponentCss;
+use crate::models::config::ProjectConfig;
+use crate::models::theme::Theme;
+use crate::utils::config::load_user_config;
+use crate::utils::integrity::verify_template_files;
+use crate::utils::stylesheet::merge_component_css;
+use crate::utils::template_finder::find_theme_dir;
+
+pub const THEMES_DIR: &str = "themes";
+
+// The theme's variables sit in the global stylesheet like a component's CSS, under this name
+const THEME_BLOCK: &str = "theme";
+
+// Themes live in templates/themes as `<name>.json` palettes
+pub fn theme_file(theme_name: &str) -> String {
+    format!("{}/{}.json", THEMES_DIR, theme_name)
+}
+
+pub fn is_theme(template_dir: &Path, theme_name: &str) -> bool {
+    template_dir.join(theme_file(theme_name)).is_file()
+}
+
+// Lists all available themes in the templates directory
+pub fn get_available_themes(template_dir: &Path) -> Result<Vec<String>> {
+    let themes_dir = template_dir.join(THEMES_DIR);
+    if !themes_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut themes = Vec::new();
+    for entry in fs::read_dir(&themes_dir)?.flatten() {
+        let path = entry.path();
+        if !path.is_file() || path.extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+        if let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) {
+            themes.push(name.to_string());
+        }
+    }
+
+    themes.sort();
+    Ok(themes)
+}
+
+pub fn load_template_theme(template_dir: &Path, theme_name: &str) -> Result<Theme> {
+    let path = template_dir.join(theme_file(theme_name));
+    let content = fs::read_to_string(&path)
+        .context(format!("Failed to read theme: {}", path.display()))?;
+
+    serde_json::from_str(&content)
+        .context(format!("Failed to parse theme: {}", path.display()))
+}
+
+// A theme by name: the project's own themes in vin-ui.json first, then the templates
+pub fn load_theme(project_config: &ProjectConfig, theme_name: &str) -> Result<Theme> {
+    if let Some(theme) = project_config.themes.get(theme_name) {
+        return Ok(theme.clone());
+    }
+
+    let Some(template_dir) = find_theme_dir(theme_name) else {
+        bail!("Theme '{}' not found, run 'vin-ui theme list' to see the available themes", theme_name);
+    };
+
+    verify_template_files(&template_dir, &[theme_file(theme_name)], &load_user_config()?)
+        .context(format!("Refusing to use theme '{}': template verification failed", theme_name))?;
+
+    load_template_theme(&template_dir, theme_name)
+}
+
+// Palette colors are CSS variables, `primary` -> `--vin-primary`
+pub fn theme_var(color: &str) -> String {
+    format!("--vin-{}", color)
+}
+
+fn theme_css(theme: &Theme) -> ComponentCss {
+    let vars_of = |colors: &BTreeMap<String, String>| {
+        colors.iter()
+            .map(|(color, value)| (theme_var(color), value.clone()))
+            .collect::<BTreeMap<_, _>>()
+    };
+
+    let mut vars = BTreeMap::new();
+    vars.insert(":root".to_string(), vars_of(&theme.colors));
+    if let Some(dark) = &theme.dark {
+        vars.insert(".dark".to_string(), vars_of(dark));
+    }
+
+    ComponentCss { vars: Some(vars), keyframes: None, layers: None }
+}
+
+// Write the theme's variables to the stylesheet, replacing the previous theme's.
+// Returns whether the file changed.
+pub fn merge_theme_css(stylesheet: &Path, theme: &Theme) -> Result<bool> {
+    merge_component_css(stylesheet, THEME_BLOCK, &theme_css(theme))
+}
+
+// The colors a template uses (from its `colors`), mapped to the arbitrary value replacing
+// them: the variable of the palette color they stand for, or with `inline` the theme's
+// color itself. Colors the theme doesn't have are left out.
+pub fn theme_substitutions(template_colors: &BTreeMap<String, String>, theme: &Theme, inline: bool) -> BTreeMap<String, String> {
+    template_colors.iter()
+        .filter_map(|(color, template_color)| {
+            let value = theme.colors.get(color)?;
+            let replacement = if inline {
+                // spaces aren't allowed in a class, Tailwind reads `_` as one
+                format!("color:{}", value.trim().replace(' ', "_"))
+            } else {
+                format!("color:var({})", theme_var(color))
+            };
+            Some((template_color.clone(), replacement))
+        })
+        .collect()
+}
@@ -1,5 +1,5 @@
 use colored::*;
-use dialoguer::Confirm;
+use dialoguer::{Confirm, Input};
 
 pub fn print_success(msg: &str) {
     println!("{} {}", "SUCCESS:".green().bold(), msg);
@@ -23,4 +23,12 @@ pub fn confirm(prompt: &str, default: bool) -> bool {
         .default(default)
         .interact()
         .unwrap_or(false)
-} 
+}
+
+pub fn input(prompt: &str, default: &str) -> String {
+    Input::new()
+        .with_prompt(prompt)
+        .default(default.to_string())
+        .interact_text()
+        .unwrap_or_else(|_| default.to_string())
+}
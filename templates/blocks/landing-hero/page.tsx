@@ -0,0 +1,15 @@
+import Button from "{%= ui_alias %}/button"
+import NeonUnderline from "{%= ui_alias %}/neon-underline"
+import { ChevronDown } from "lucide-react"
+
+export default function Page() {
+    return (
+        <main className="relative flex min-h-screen flex-col items-center justify-center gap-10 bg-black px-6 text-white">
+            <h1 className="text-center text-5xl font-bold tracking-tight">
+                <NeonUnderline>{%= title %}</NeonUnderline>
+            </h1>
+            <Button href="https://github.com" text="Get started" />
+            <ChevronDown className="absolute bottom-8 h-6 w-6 animate-bounce text-white/60" />
+        </main>
+    )
+}
@@ -0,0 +1,31 @@
+import Link from "next/link";
+import React from "react";
+import styles from "./button.module.css";
+
+interface ThreeDBtnProps {
+    href?: string;
+    text?: string;
+    variant?: "black" | "white" | "purple" | "blue" | "red";
+    className?: string;
+}
+
+const Button = ({
+    href,
+    text,
+    variant = "purple",
+    className = "",
+}: ThreeDBtnProps) => {
+    const frameClasses = [styles.button, styles[variant], className].filter(Boolean).join(" ");
+
+    const handleClick = () => {
+        window.open(href, '_blank', 'noopener,noreferrer');
+    };
+
+    return (
+        <div className={frameClasses} onClick={handleClick}>
+            <Link target="_blank" href={href || ""}>{text || "This is a lil cute button"}</Link>
+        </div>
+    )
+}
+
+export default Button
@@ -0,0 +1,17 @@
+"use client"
+
+import { useEffect, useState } from "react"
+
+/**
+ * Whether the component has mounted on the client.
+ * Useful to avoid hydration mismatches for client-only values.
+ */
+export function useMounted() {
+    const [mounted, setMounted] = useState(false)
+
+    useEffect(() => {
+        setMounted(true)
+    }, [])
+
+    return mounted
+}
@@ -3,8 +3,8 @@
 import React, { useCallback, useEffect, useRef } from 'react';
 import { twMerge } from 'tailwind-merge';
 // import ImageCarousel from './ImageCarousel';
-import { timelineData } from "./utils/timeline-data"
-import "./timeline.css"
+import { timelineData } from "./timeline-data"
+import "./neon-timeline.css"
 
 // Utility to sanitize HTML content for safe rendering
 const sanitizeHtml = (html: string) => {